@@ -1,12 +1,11 @@
-use rsxiv::id::OldId;
+use rsxiv::id::ArticleId;
 use std::hint::black_box;
-use std::str::FromStr;
 
 use criterion::{Criterion, criterion_group, criterion_main};
 
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("old-id", |b| {
-        b.iter(|| OldId::from_str(black_box("hep-th/0109001")))
+        b.iter(|| ArticleId::parse(black_box("hep-th/0109001")))
     });
 }
 
@@ -0,0 +1,308 @@
+//! # Blocking arXiv API client
+//!
+//! This module provides a blocking [`Client`] which sends [`Query`]s to the arXiv API and parses
+//! the responses. The network request itself is made by a [`Transport`], so that any HTTP library
+//! can be used.
+//!
+//! The following transports are provided:
+//!
+//! - [`MemoryTransport`], which serves fixed responses from memory and is useful for testing.
+//! - [`UreqTransport`], which is backed by [`ureq`](https://crates.io/crates/ureq) and requires
+//!   the `ureq` feature.
+//!
+//! Wrap a transport in [`RateLimited`] to follow the arXiv API guidelines, which ask clients to
+//! send one request at a time and to wait three seconds between requests.
+//!
+//! Wrap a transport in [`Cached`] to store responses on disk, so that repeated queries do not
+//! send a request.
+//!
+//! To keep a local copy of the metadata in a set of categories up to date, use a [`Harvester`].
+//!
+//! With the `async` feature, the [`AsyncClient`] sends queries using an [`AsyncTransport`], and
+//! can return a stream of every entry matching a query. Wrap an asynchronous transport in [`AsyncRateLimited`] to
+//! follow the arXiv API guidelines.
+//!
+//! ### Example
+//! ```
+//! use rsxiv::{
+//!     client::{Client, MemoryTransport},
+//!     id::ArticleId,
+//!     query::Query,
+//! };
+//!
+//! let mut query = Query::new();
+//! query.id_list().push(&ArticleId::parse("2206.06921").unwrap());
+//!
+//! // abridged arXiv response
+//! let xml = // r#"<feed xmlns...
+#![doc = include_str!("response/tests/query_doc.txt")]
+//! let mut transport = MemoryTransport::new();
+//! transport.insert(&query, xml);
+//!
+//! let client = Client::new(transport);
+//! let mut buffer = Vec::new();
+//! let response = client.query(&query, &mut buffer).unwrap();
+//! assert_eq!(response.entries[0].id, ArticleId::parse("2206.06921v3").unwrap());
+//! ```
+mod cache;
+mod harvest;
+mod memory;
+mod rate;
+#[cfg(feature = "async")]
+mod stream;
+#[cfg(test)]
+mod tests;
+#[cfg(feature = "ureq")]
+mod ureq;
+
+use std::{
+    fmt::Display,
+    fs, io,
+    path::Path,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use url::Url;
+
+pub use self::cache::{CacheError, CacheMode, Cached, CachedResponse};
+pub use self::harvest::{Checkpoint, Harvester};
+pub use self::memory::{MemoryTransport, MissingFixture};
+pub use self::rate::{Clock, FakeClock, Permit, RateLimited, RateLimiter, SystemClock};
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub use self::stream::{AsyncClient, AsyncRateLimited, AsyncTransport, EntryStream};
+#[cfg(feature = "ureq")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ureq")))]
+pub use self::ureq::UreqTransport;
+use crate::{
+    query::Query,
+    response::{Entry, Response, ResponseError, parse},
+};
+
+/// The number of times an empty page before the end of the results is requested again before
+/// [`ClientError::EmptyPage`] is returned.
+///
+/// The arXiv API occasionally returns an empty page even though further results exist.
+const EMPTY_PAGE_RETRIES: u32 = 3;
+
+/// Write the contents to a uniquely named temporary file next to the path, and then rename it to
+/// the path, so that a partially written file is never read.
+fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    /// A counter which distinguishes concurrent writes within this process.
+    static WRITES: AtomicU64 = AtomicU64::new(0);
+
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(
+        ".{}-{}.tmp",
+        std::process::id(),
+        WRITES.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = fs::write(&tmp, contents).and_then(|()| fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// An HTTP request to the arXiv API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Request {
+    /// A `GET` request, with the query parameters encoded in the URL.
+    Get {
+        /// The request URL.
+        url: Url,
+    },
+    /// A `POST` request, with the query parameters encoded in the body.
+    Post {
+        /// The request URL.
+        url: Url,
+        /// The request body, with content type `application/x-www-form-urlencoded`.
+        body: String,
+    },
+}
+
+impl Request {
+    /// Construct the request which sends the query, using a `POST` request if `post` is `true`.
+    #[must_use]
+    pub fn new(query: &Query, post: bool) -> Self {
+        if post {
+            Request::Post {
+                url: query.endpoint(),
+                body: query.form_body(),
+            }
+        } else {
+            Request::Get { url: query.url() }
+        }
+    }
+
+    /// The URL of the request.
+    #[must_use]
+    pub fn url(&self) -> &Url {
+        match self {
+            Request::Get { url } | Request::Post { url, .. } => url,
+        }
+    }
+
+    /// The URL of the equivalent `GET` request.
+    ///
+    /// For a `POST` request, this is the URL with the body appended as the query string.
+    #[must_use]
+    pub fn get_url(&self) -> Url {
+        match self {
+            Request::Get { url } => url.clone(),
+            Request::Post { url, body } => {
+                let mut url = url.clone();
+                url.set_query(Some(body));
+                url
+            }
+        }
+    }
+}
+
+/// A transport which sends [`Request`]s and returns the response body.
+///
+/// Implement this trait to use any HTTP library with a [`Client`].
+pub trait Transport {
+    /// The error returned if the request fails.
+    type Error;
+
+    /// Send the request, returning the body of the response.
+    fn send(&self, request: &Request) -> Result<Vec<u8>, Self::Error>;
+
+    /// Returns if the error indicates that the server is temporarily unavailable, such as an HTTP
+    /// 503 response, so that the request may be retried later.
+    ///
+    /// This is used by [`RateLimited`] to back off. The default implementation returns `false`.
+    fn is_unavailable(error: &Self::Error) -> bool {
+        let _ = error;
+        false
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for &T {
+    type Error = T::Error;
+
+    fn send(&self, request: &Request) -> Result<Vec<u8>, Self::Error> {
+        (**self).send(request)
+    }
+
+    fn is_unavailable(error: &Self::Error) -> bool {
+        T::is_unavailable(error)
+    }
+}
+
+/// An error which results from a [`Client`] request.
+#[derive(Debug)]
+pub enum ClientError<E> {
+    /// The transport failed to send the request.
+    Transport(E),
+    /// The response could not be parsed.
+    Response(ResponseError),
+    /// The arXiv API repeatedly returned an empty page before the end of the results. Contains
+    /// the `start` of the page.
+    EmptyPage(u64),
+}
+
+impl<E> From<ResponseError> for ClientError<E> {
+    fn from(value: ResponseError) -> Self {
+        Self::Response(value)
+    }
+}
+
+impl<E: Display> Display for ClientError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Transport(err) => write!(f, "request failed: {err}"),
+            ClientError::Response(err) => write!(f, "invalid response: {err}"),
+            ClientError::EmptyPage(start) => {
+                write!(f, "empty page at {start} before the end of the results")
+            }
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ClientError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Transport(err) => Some(err),
+            ClientError::Response(err) => Some(err),
+            ClientError::EmptyPage(_) => None,
+        }
+    }
+}
+
+/// A blocking client which sends [`Query`]s using a [`Transport`] and parses the responses.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone)]
+pub struct Client<T> {
+    transport: T,
+    post: bool,
+}
+
+impl<T: Transport> Client<T> {
+    /// Construct a new client using the provided transport.
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            post: false,
+        }
+    }
+
+    /// Send queries as `POST` requests instead of `GET` requests.
+    ///
+    /// This avoids length limits on the URL when requesting long identifier lists.
+    pub fn post(&mut self, post: bool) -> &mut Self {
+        self.post = post;
+        self
+    }
+
+    /// Returns a reference to the underlying transport.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Returns the underlying transport.
+    pub fn into_transport(self) -> T {
+        self.transport
+    }
+
+    /// The request used to send the query.
+    #[must_use]
+    pub fn request(&self, query: &Query) -> Request {
+        Request::new(query, self.post)
+    }
+
+    /// Send the query and return the raw response body.
+    pub fn fetch(&self, query: &Query) -> Result<Vec<u8>, ClientError<T::Error>> {
+        self.transport
+            .send(&self.request(query))
+            .map_err(ClientError::Transport)
+    }
+
+    /// Send the query and parse the response.
+    ///
+    /// The response body is written into `buffer`, replacing its contents, and the entries borrow
+    /// from the buffer.
+    pub fn query<'b>(
+        &self,
+        query: &Query,
+        buffer: &'b mut Vec<u8>,
+    ) -> Result<Response<Vec<Entry<'b>>>, ClientError<T::Error>> {
+        *buffer = self.fetch(query)?;
+        Ok(parse(buffer)?)
+    }
+
+    /// Send the query and deserialize the response.
+    ///
+    /// See the [`de`](crate::de) module for more detail on deserialization.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn deserialize<D: serde::de::DeserializeOwned>(
+        &self,
+        query: &Query,
+    ) -> Result<Response<D>, ClientError<T::Error>> {
+        let body = self.fetch(query)?;
+        Ok(Response::from_xml(&body)?)
+    }
+}
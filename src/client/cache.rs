@@ -0,0 +1,326 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use chrono::{DateTime, FixedOffset};
+
+use super::{Clock, Request, SystemClock, Transport, write_atomic};
+use crate::{
+    query::{Query, fnv1a},
+    response::ResponseReader,
+};
+
+/// How a [`Cached`] transport uses the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    /// Serve cached responses which have not expired, and send the request otherwise.
+    #[default]
+    Normal,
+    /// Serve cached responses, even if they have expired, and never send a request.
+    OfflineOnly,
+    /// Always send the request, replacing any cached response.
+    Refresh,
+}
+
+/// A response stored in the cache of a [`Cached`] transport.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    /// The raw Atom response body.
+    pub body: Vec<u8>,
+    /// The `updated` timestamp of the response feed.
+    pub updated: DateTime<FixedOffset>,
+    /// When the response was stored in the cache.
+    pub stored: SystemTime,
+}
+
+/// An error which results from a request sent through a [`Cached`] transport.
+#[derive(Debug)]
+pub enum CacheError<E> {
+    /// The inner transport failed to send the request.
+    Transport(E),
+    /// The cache could not be read or written.
+    Io(io::Error),
+    /// The transport is in [offline mode](CacheMode::OfflineOnly), and the response was not
+    /// cached.
+    Missing(Request),
+}
+
+impl<E> From<io::Error> for CacheError<E> {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl<E: Display> Display for CacheError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CacheError::Transport(err) => err.fmt(f),
+            CacheError::Io(err) => write!(f, "cache error: {err}"),
+            CacheError::Missing(request) => {
+                write!(f, "no cached response for '{}'", request.get_url())
+            }
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for CacheError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CacheError::Transport(err) => Some(err),
+            CacheError::Io(err) => Some(err),
+            CacheError::Missing(_) => None,
+        }
+    }
+}
+
+/// A [`Transport`] which stores responses in a directory, and serves them without sending the
+/// request again.
+///
+/// Responses are keyed by the [`get_url`](Request::get_url) of the request, so a `GET` request and
+/// the equivalent `POST` request share a cached response. Each response is stored as a separate
+/// file containing the raw response body followed by an XML comment recording the URL, which can
+/// be read with [`parse`](crate::response::parse) or
+/// [`Response::from_xml`](crate::response::Response::from_xml). Only well-formed responses with a
+/// valid feed header are stored, so error pages and arXiv API errors are never cached.
+///
+/// A cached response expires once it is older than the [time-to-live](Cached::ttl), as measured by
+/// the [`Clock`] of the transport. To improve the hit rate, [canonicalize](Query::canonicalize)
+/// queries before sending them.
+///
+/// ### Example
+/// ```
+/// use rsxiv::{
+///     client::{CacheMode, Cached, Client, MemoryTransport},
+///     id::ArticleId,
+///     query::Query,
+/// };
+///
+/// let mut query = Query::new();
+/// query.id_list().push(&ArticleId::parse("2206.06921").unwrap());
+///
+/// // abridged arXiv response
+/// let xml = // r#"<feed xmlns...
+#[doc = include_str!("../response/tests/query_doc.txt")]
+/// let mut transport = MemoryTransport::new();
+/// transport.insert(&query, xml);
+///
+/// let dir = std::env::temp_dir().join("rsxiv-cache-doc");
+/// let client = Client::new(Cached::new(transport, &dir));
+/// let mut buffer = Vec::new();
+/// client.query(&query, &mut buffer).unwrap();
+///
+/// // read the response back without sending a request
+/// let mut offline = Cached::new(MemoryTransport::new(), &dir);
+/// offline.mode(CacheMode::OfflineOnly);
+/// let client = Client::new(offline);
+/// let response = client.query(&query, &mut buffer).unwrap();
+/// assert_eq!(response.entries[0].id, ArticleId::parse("2206.06921v3").unwrap());
+/// # std::fs::remove_dir_all(dir).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cached<T, C = SystemClock> {
+    transport: T,
+    dir: PathBuf,
+    ttl: Option<Duration>,
+    mode: CacheMode,
+    clock: C,
+}
+
+impl<T: Transport> Cached<T> {
+    /// Wrap the transport with a cache stored in the directory, using the [`SystemClock`].
+    ///
+    /// The directory is created when the first response is stored. Cached responses expire after
+    /// [one day](Self::DEFAULT_TTL).
+    pub fn new<P: Into<PathBuf>>(transport: T, dir: P) -> Self {
+        Self::with_clock(transport, dir, SystemClock)
+    }
+}
+
+impl<T: Transport, C: Clock> Cached<T, C> {
+    /// The default time-to-live of a cached response.
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+    /// Wrap the transport with a cache stored in the directory, using the provided clock to
+    /// timestamp and expire responses.
+    ///
+    /// The directory is created when the first response is stored. Cached responses expire after
+    /// [one day](Self::DEFAULT_TTL).
+    pub fn with_clock<P: Into<PathBuf>>(transport: T, dir: P, clock: C) -> Self {
+        Self {
+            transport,
+            dir: dir.into(),
+            ttl: Some(Self::DEFAULT_TTL),
+            mode: CacheMode::Normal,
+            clock,
+        }
+    }
+
+    /// Set the time-to-live of cached responses, or `None` if cached responses never expire.
+    pub fn ttl(&mut self, ttl: Option<Duration>) -> &mut Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Set how the cache is used.
+    pub fn mode(&mut self, mode: CacheMode) -> &mut Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the clock of the cache.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Returns a reference to the underlying transport.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Returns the underlying transport.
+    pub fn into_transport(self) -> T {
+        self.transport
+    }
+
+    /// The path of the file which stores the response to the request.
+    ///
+    /// The file name is a hash of the URL, so distinct requests may share a path. A cached
+    /// response is only served for the URL recorded in the file.
+    #[must_use]
+    pub fn path(&self, request: &Request) -> PathBuf {
+        let hash = fnv1a(request.get_url().as_str().as_bytes());
+        self.dir.join(format!("{hash:016x}.xml"))
+    }
+
+    /// Read the cached response to the query, whether or not it has expired.
+    ///
+    /// Returns `Ok(None)` if no response is cached.
+    pub fn get(&self, query: &Query) -> io::Result<Option<CachedResponse>> {
+        self.read(&Request::new(query, false))
+    }
+
+    /// Remove the cached response to the query, if any.
+    pub fn remove(&self, query: &Query) -> io::Result<()> {
+        match fs::remove_file(self.path(&Request::new(query, false))) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns if the cached response has expired.
+    fn is_expired(&self, cached: &CachedResponse) -> bool {
+        self.ttl.is_some_and(|ttl| {
+            self.clock
+                .system_time()
+                .duration_since(cached.stored)
+                .is_ok_and(|age| age >= ttl)
+        })
+    }
+
+    fn read(&self, request: &Request) -> io::Result<Option<CachedResponse>> {
+        let path = self.path(request);
+        let mut body = match fs::read(&path) {
+            Ok(body) => body,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let stored = fs::metadata(&path)?.modified()?;
+
+        // a file stored for a different URL with the same hash, or a corrupted file, is treated
+        // as a missing response
+        let Some(len) = body
+            .strip_suffix(url_comment(request).as_bytes())
+            .map(<[u8]>::len)
+        else {
+            return Ok(None);
+        };
+        body.truncate(len);
+
+        Ok(feed_updated(&body).map(|updated| CachedResponse {
+            body,
+            updated,
+            stored,
+        }))
+    }
+
+    fn write(&self, request: &Request, body: &[u8]) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path(request);
+        write_atomic(&path, &[body, url_comment(request).as_bytes()].concat())?;
+        // the modification time records when the response was stored
+        fs::File::options()
+            .write(true)
+            .open(&path)?
+            .set_modified(self.clock.system_time())
+    }
+}
+
+/// The XML comment appended to a cached response to record the URL of the request.
+///
+/// The `-` character is percent-encoded, since `--` cannot appear in an XML comment.
+fn url_comment(request: &Request) -> String {
+    format!(
+        "\n<!-- {} -->\n",
+        request.get_url().as_str().replace('-', "%2D")
+    )
+}
+
+/// The `updated` timestamp of the response feed, or `None` if the feed header is invalid.
+///
+/// The entries are not read, since they were validated by [`is_cacheable`] when the response was
+/// stored.
+fn feed_updated(body: &[u8]) -> Option<DateTime<FixedOffset>> {
+    ResponseReader::init(body).ok().map(|(updated, ..)| updated)
+}
+
+/// Returns if the response is well-formed and is not an arXiv API error.
+fn is_cacheable(body: &[u8]) -> bool {
+    let Ok((_, _, mut reader)) = ResponseReader::init(body) else {
+        return false;
+    };
+    loop {
+        match reader.next_raw_entry() {
+            Ok(Some(_)) => {}
+            Ok(None) => return true,
+            Err(_) => return false,
+        }
+    }
+}
+
+impl<T: Transport, C: Clock> Transport for Cached<T, C> {
+    type Error = CacheError<T::Error>;
+
+    fn send(&self, request: &Request) -> Result<Vec<u8>, Self::Error> {
+        if self.mode != CacheMode::Refresh
+            && let Some(cached) = self.read(request)?
+            && (self.mode == CacheMode::OfflineOnly || !self.is_expired(&cached))
+        {
+            return Ok(cached.body);
+        }
+
+        if self.mode == CacheMode::OfflineOnly {
+            return Err(CacheError::Missing(request.clone()));
+        }
+
+        let body = self
+            .transport
+            .send(request)
+            .map_err(CacheError::Transport)?;
+        if is_cacheable(&body) {
+            self.write(request, &body)?;
+        }
+        Ok(body)
+    }
+
+    fn is_unavailable(error: &Self::Error) -> bool {
+        matches!(error, CacheError::Transport(err) if T::is_unavailable(err))
+    }
+}
@@ -0,0 +1,276 @@
+use std::{collections::HashMap, fmt::Display, fs, io, path::Path};
+
+use chrono::NaiveDateTime;
+
+use super::{Client, ClientError, EMPTY_PAGE_RETRIES, Transport, write_atomic};
+use crate::{
+    id::ArticleId,
+    query::{Category, MAX_RESULTS, MAX_START, Query},
+    response::Entry,
+};
+
+/// The format of the timestamp in a saved [`Checkpoint`].
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// The progress of a [`Harvester`], which determines the entries returned by the next harvest.
+///
+/// A checkpoint records the latest `updated` timestamp of the harvested entries, along with the
+/// identifiers of the entries which were updated at exactly that time. The next harvest only
+/// returns entries which were updated later, or which were updated at that time but not yet
+/// returned.
+///
+/// A checkpoint can be persisted with [`Checkpoint::save`] and restored with
+/// [`Checkpoint::load`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// The latest `updated` timestamp of the harvested entries, in UTC.
+    pub updated: NaiveDateTime,
+    /// The versionless identifiers of the harvested entries which were updated at exactly
+    /// `updated`.
+    pub seen: Vec<ArticleId>,
+}
+
+impl Checkpoint {
+    /// Construct a checkpoint which harvests every entry updated since the provided time, in
+    /// UTC.
+    #[must_use]
+    pub fn new(since: NaiveDateTime) -> Self {
+        Self {
+            updated: since,
+            seen: Vec::new(),
+        }
+    }
+
+    /// Returns if an entry updated at the provided time has not yet been harvested.
+    ///
+    /// The version of the identifier is ignored.
+    #[must_use]
+    pub fn is_new(&self, updated: NaiveDateTime, id: ArticleId) -> bool {
+        updated > self.updated
+            || (updated == self.updated && !self.seen.contains(&id.clear_version()))
+    }
+
+    /// Record that an entry updated at the provided time was harvested.
+    ///
+    /// The version of the identifier is ignored.
+    pub fn record(&mut self, updated: NaiveDateTime, id: ArticleId) {
+        let id = id.clear_version();
+        if updated > self.updated {
+            self.updated = updated;
+            self.seen.clear();
+        }
+        if updated == self.updated && !self.seen.contains(&id) {
+            self.seen.push(id);
+        }
+    }
+
+    /// Parse a checkpoint in the format written by the [`Display`] implementation, returning
+    /// `None` if the checkpoint is invalid.
+    ///
+    /// The first line is the timestamp, and each subsequent line is an identifier.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut lines = s.lines().filter(|line| !line.is_empty());
+        let updated = NaiveDateTime::parse_from_str(lines.next()?, TIMESTAMP_FORMAT).ok()?;
+        let seen = lines
+            .map(|line| ArticleId::parse(line).ok().map(ArticleId::clear_version))
+            .collect::<Option<_>>()?;
+        Some(Self { updated, seen })
+    }
+
+    /// Read a checkpoint from the file.
+    ///
+    /// Returns an error of kind [`InvalidData`](io::ErrorKind::InvalidData) if the file does not
+    /// contain a valid checkpoint.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid checkpoint"))
+    }
+
+    /// Write the checkpoint to the file, replacing it if it exists.
+    ///
+    /// The checkpoint is written to a uniquely named temporary file first, so an interrupted or
+    /// concurrent write does not corrupt an existing checkpoint.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        write_atomic(path.as_ref(), self.to_string().as_bytes())
+    }
+}
+
+impl Display for Checkpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.updated.format(TIMESTAMP_FORMAT))?;
+        for id in &self.seen {
+            writeln!(f, "{id}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An incremental harvester for the metadata of the articles in a set of categories.
+///
+/// Each harvest requests the articles which were updated since the [`Checkpoint`], using
+/// [`Query::category_updates`], and pages through the results in order of update. If there are too
+/// many results to page through, a new query is started from the latest update seen so far.
+/// Entries are deduplicated by versionless identifier, and the checkpoint is advanced as entries
+/// are returned.
+///
+/// ### Example
+/// ```
+/// use chrono::NaiveDate;
+/// use rsxiv::{
+///     client::{Checkpoint, Client, Harvester, MemoryTransport},
+///     query::Category,
+/// };
+///
+/// // abridged arXiv response
+/// let xml = // r#"<feed xmlns...
+#[doc = include_str!("../response/tests/query_doc.txt")]
+/// let mut transport = MemoryTransport::new();
+/// transport.fallback(xml);
+///
+/// let categories = ["math.CA", "math.DS"].map(|c| Category::from_id(c).unwrap());
+/// let harvester = Harvester::new(Client::new(transport), categories);
+///
+/// let since = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+/// let mut checkpoint = Checkpoint::new(since);
+/// let mut titles = Vec::new();
+/// harvester
+///     .harvest(&mut checkpoint, |entry| titles.push(entry.title.to_string()))
+///     .unwrap();
+/// assert_eq!(titles.len(), 1);
+///
+/// // the entry is not returned again
+/// assert_eq!(harvester.harvest(&mut checkpoint, |_| {}).unwrap(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Harvester<T> {
+    client: Client<T>,
+    categories: Vec<Category>,
+    page_size: u16,
+}
+
+impl<T: Transport> Harvester<T> {
+    /// Construct a new harvester for the categories, which sends requests with the client.
+    ///
+    /// Each page requests `2000` entries, the largest page size permitted by the arXiv API.
+    pub fn new<I: IntoIterator<Item = Category>>(client: Client<T>, categories: I) -> Self {
+        Self {
+            client,
+            categories: categories.into_iter().collect(),
+            page_size: MAX_RESULTS,
+        }
+    }
+
+    /// Set the number of entries requested by each page.
+    ///
+    /// Returns `None` if `max_results` is `0` or exceeds `2000`, in which case the page size is not
+    /// updated.
+    pub fn page_size(&mut self, max_results: u16) -> Option<&mut Self> {
+        if max_results > 0 && max_results <= MAX_RESULTS {
+            self.page_size = max_results;
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the client.
+    pub fn client(&self) -> &Client<T> {
+        &self.client
+    }
+
+    /// Request every entry updated since the checkpoint, calling `emit` with each new or updated
+    /// entry and advancing the checkpoint.
+    ///
+    /// Returns the number of entries emitted. If a request fails, the error is returned and the
+    /// checkpoint reflects the entries which were already emitted, so the harvest can be resumed.
+    /// If there are no categories, no requests are sent.
+    ///
+    /// The arXiv API occasionally returns an empty page before the end of the results, in which
+    /// case the page is requested again, and [`ClientError::EmptyPage`] is returned if the page is
+    /// still empty after a few attempts. Wrap the transport in a
+    /// [`RateLimited`](super::RateLimited) so that these requests are also throttled.
+    pub fn harvest<F>(
+        &self,
+        checkpoint: &mut Checkpoint,
+        mut emit: F,
+    ) -> Result<usize, ClientError<T::Error>>
+    where
+        F: FnMut(&Entry<'_>),
+    {
+        let since = checkpoint.clone();
+        let mut emitted: HashMap<ArticleId, NaiveDateTime> = HashMap::new();
+        let mut count = 0;
+        let mut buffer = Vec::new();
+
+        loop {
+            let window_start = checkpoint.updated;
+            let Some(mut query) =
+                Query::category_updates(self.categories.iter().copied(), window_start)
+            else {
+                return Ok(count);
+            };
+
+            let mut start = 0;
+            let mut empty_pages = 0;
+            loop {
+                query
+                    .paginate(start, self.page_size)
+                    .expect("page size is valid");
+                let response = self.client.query(&query, &mut buffer)?;
+
+                let pagination = &response.pagination;
+                if response.entries.is_empty() && pagination.start_index < pagination.total_results
+                {
+                    // request the same page again
+                    empty_pages += 1;
+                    if empty_pages > EMPTY_PAGE_RETRIES {
+                        return Err(ClientError::EmptyPage(u64::from(start)));
+                    }
+                    continue;
+                }
+                empty_pages = 0;
+
+                for entry in &response.entries {
+                    let updated = entry.updated.naive_utc();
+                    let id = entry.id.clear_version();
+                    if !since.is_new(updated, id)
+                        || emitted.get(&id).is_some_and(|prev| *prev >= updated)
+                    {
+                        continue;
+                    }
+
+                    emit(entry);
+                    count += 1;
+                    emitted.insert(id, updated);
+                    checkpoint.record(updated, id);
+                }
+
+                let received = response.entries.len() as u64;
+                let next_start = u64::from(start) + received;
+                if received == 0 || next_start >= response.pagination.total_results {
+                    return Ok(count);
+                }
+
+                match u16::try_from(next_start) {
+                    Ok(next_start) if next_start <= MAX_START => start = next_start,
+                    _ => break,
+                }
+            }
+
+            // the query cannot be paged further, so start a new query from the latest update; if
+            // there are too many updates within the same minute, the remainder cannot be retrieved
+            if !same_minute(checkpoint.updated, window_start) {
+                continue;
+            }
+            return Ok(count);
+        }
+    }
+}
+
+/// Returns if the timestamps are within the same minute, which is the granularity of date ranges
+/// in search queries.
+fn same_minute(a: NaiveDateTime, b: NaiveDateTime) -> bool {
+    let minute = |t: NaiveDateTime| t.and_utc().timestamp().div_euclid(60);
+    minute(a) == minute(b)
+}
@@ -0,0 +1,76 @@
+use std::{collections::HashMap, fmt::Display, sync::Mutex};
+
+use super::{Request, Transport};
+use crate::query::Query;
+
+/// A [`Transport`] which serves fixed responses from memory.
+///
+/// Responses are registered for a [`Query`] using [`MemoryTransport::insert`], and are matched
+/// against the [`get_url`](Request::get_url) of each request, so the same response is served for
+/// `GET` and `POST` requests. Every request sent through the transport is recorded, and can be
+/// inspected with [`MemoryTransport::requests`].
+#[derive(Debug, Default)]
+pub struct MemoryTransport {
+    responses: HashMap<String, Vec<u8>>,
+    fallback: Option<Vec<u8>>,
+    requests: Mutex<Vec<Request>>,
+}
+
+/// The error returned by a [`MemoryTransport`] when no response was registered for a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingFixture(pub Request);
+
+impl Display for MissingFixture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "no response registered for '{}'", self.0.get_url())
+    }
+}
+
+impl std::error::Error for MissingFixture {}
+
+impl MemoryTransport {
+    /// Construct a new transport with no registered responses.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the response body to serve for the query.
+    pub fn insert<B: Into<Vec<u8>>>(&mut self, query: &Query, body: B) -> &mut Self {
+        self.responses.insert(query.url().into(), body.into());
+        self
+    }
+
+    /// Register the response body to serve for any request which does not match a registered
+    /// query.
+    pub fn fallback<B: Into<Vec<u8>>>(&mut self, body: B) -> &mut Self {
+        self.fallback = Some(body.into());
+        self
+    }
+
+    /// Returns the requests which were sent through the transport, in order.
+    #[must_use]
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+}
+
+impl Transport for MemoryTransport {
+    type Error = MissingFixture;
+
+    fn send(&self, request: &Request) -> Result<Vec<u8>, Self::Error> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(request.clone());
+
+        self.responses
+            .get(request.get_url().as_str())
+            .or(self.fallback.as_ref())
+            .cloned()
+            .ok_or_else(|| MissingFixture(request.clone()))
+    }
+}
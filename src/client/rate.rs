@@ -0,0 +1,530 @@
+use std::{
+    future::{Future, poll_fn},
+    hash::{BuildHasher, RandomState},
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    task::{Poll, Waker},
+    time::{Duration, Instant, SystemTime},
+};
+
+use super::{Request, Transport};
+
+/// A source of time for a [`RateLimiter`] or a [`Cached`](super::Cached) transport.
+///
+/// Use [`SystemClock`] to wait in real time, and [`FakeClock`] in tests.
+pub trait Clock {
+    /// The current time.
+    fn now(&self) -> Instant;
+
+    /// The current time as a [`SystemTime`], which can be compared with file timestamps.
+    fn system_time(&self) -> SystemTime;
+
+    /// Block the current thread for the duration.
+    fn sleep(&self, duration: Duration);
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        (**self).system_time()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration);
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        (**self).system_time()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration);
+    }
+}
+
+/// A [`Clock`] which uses the system time and sleeps the current thread.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// A [`Clock`] which only advances when slept, so that rate limiting and cache expiry can be
+/// tested without waiting.
+#[derive(Debug)]
+pub struct FakeClock {
+    start: Instant,
+    system_start: SystemTime,
+    elapsed: Mutex<Duration>,
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeClock {
+    /// Construct a new clock starting at the current time.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            system_start: SystemTime::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// The total time the clock has advanced since it was constructed.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Advance the clock by the duration, as if time passed while doing other work.
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap_or_else(|err| err.into_inner()) += duration;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        self.system_start + self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
+
+/// The mutable state of a [`RateLimiter`].
+#[derive(Debug)]
+struct LimiterState {
+    /// The earliest time at which the next request may be sent.
+    next: Option<Instant>,
+    /// The number of consecutive requests which failed because the server was unavailable.
+    failures: u32,
+    /// The state of the xorshift generator used for jitter.
+    rng: u64,
+    /// Whether a permit is currently held.
+    held: bool,
+    /// The tasks waiting in [`RateLimiter::acquire_async`] for the permit to be released.
+    wakers: Vec<Waker>,
+}
+
+/// A rate limiter which enforces the arXiv API guidelines for polite clients.
+///
+/// The arXiv API asks clients to send one request at a time, and to wait three seconds between
+/// requests. A [`Permit`] returned by [`RateLimiter::acquire`] is required to send a request: only
+/// one permit exists at a time, and the next permit is not granted until the configured delay has
+/// passed since the previous permit was released.
+///
+/// The delay is increased by a random jitter, and grows exponentially after each response
+/// indicating that the server is unavailable (HTTP 503), up to a maximum. The limiter is [`Sync`],
+/// so it can be shared between threads in an [`Arc`]. Asynchronous code can wait for a permit
+/// without blocking the thread using [`RateLimiter::acquire_async`].
+///
+/// See [`RateLimited`] for a [`Transport`] which applies a rate limiter to every request.
+///
+/// ### Example
+/// ```
+/// use std::time::Duration;
+///
+/// use rsxiv::client::{FakeClock, RateLimiter};
+///
+/// let limiter = RateLimiter::with_clock(FakeClock::new());
+///
+/// for _ in 0..3 {
+///     let permit = limiter.acquire();
+///     // send the request
+///     drop(permit);
+/// }
+/// assert_eq!(limiter.clock().elapsed(), Duration::from_secs(6));
+///
+/// // back off after the server is unavailable
+/// limiter.acquire().unavailable();
+/// drop(limiter.acquire());
+/// assert_eq!(limiter.clock().elapsed(), Duration::from_secs(15));
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter<C = SystemClock> {
+    clock: C,
+    delay: Duration,
+    jitter: Duration,
+    max_backoff: Duration,
+    state: Mutex<LimiterState>,
+    released: Condvar,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RateLimiter {
+    /// Construct a new rate limiter using the [`SystemClock`] and the default configuration.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+}
+
+impl<C: Clock> RateLimiter<C> {
+    /// The default delay between requests, as requested by the arXiv API guidelines.
+    pub const DEFAULT_DELAY: Duration = Duration::from_secs(3);
+
+    /// The default maximum delay after repeated unavailable responses.
+    pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(120);
+
+    /// Construct a new rate limiter using the provided clock.
+    ///
+    /// The limiter has a delay of [three seconds](Self::DEFAULT_DELAY), no jitter, and a
+    /// [maximum backoff](Self::DEFAULT_MAX_BACKOFF) of two minutes.
+    pub fn with_clock(clock: C) -> Self {
+        Self {
+            clock,
+            delay: Self::DEFAULT_DELAY,
+            jitter: Duration::ZERO,
+            max_backoff: Self::DEFAULT_MAX_BACKOFF,
+            state: Mutex::new(LimiterState {
+                next: None,
+                failures: 0,
+                rng: RandomState::new().hash_one(0u8) | 1,
+                held: false,
+                wakers: Vec::new(),
+            }),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Set the minimum delay between the end of one request and the start of the next.
+    pub fn delay(&mut self, delay: Duration) -> &mut Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Set the maximum random jitter added to each delay.
+    ///
+    /// The jitter is chosen uniformly between zero and the maximum, and prevents independent
+    /// clients from sending requests in lockstep.
+    pub fn jitter(&mut self, jitter: Duration) -> &mut Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the maximum delay after repeated unavailable responses.
+    ///
+    /// After `n` consecutive unavailable responses, the delay is `2ⁿ` times the configured delay,
+    /// up to this maximum.
+    pub fn max_backoff(&mut self, max_backoff: Duration) -> &mut Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Set the seed of the random number generator used for jitter.
+    ///
+    /// By default, the generator is seeded randomly.
+    pub fn seed(&mut self, seed: u64) -> &mut Self {
+        // xorshift requires a non-zero state
+        self.state
+            .get_mut()
+            .unwrap_or_else(|err| err.into_inner())
+            .rng = seed | 1;
+        self
+    }
+
+    /// Returns a reference to the clock.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// The number of consecutive requests which failed because the server was unavailable.
+    pub fn failures(&self) -> u32 {
+        self.lock().failures
+    }
+
+    /// Wait until a request may be sent, and return a permit to send it.
+    ///
+    /// Blocks while another permit is held, and then sleeps until the delay after the previous
+    /// request has passed. The delay before the next request starts when the permit is dropped.
+    pub fn acquire(&self) -> Permit<'_, C> {
+        loop {
+            let mut state = self.lock();
+            while state.held {
+                state = self
+                    .released
+                    .wait(state)
+                    .unwrap_or_else(|err| err.into_inner());
+            }
+
+            match self.try_acquire(&mut state) {
+                Ok(permit) => return permit,
+                Err(wait) => {
+                    drop(state);
+                    self.clock.sleep(wait);
+                }
+            }
+        }
+    }
+
+    /// Wait until a request may be sent without blocking the thread, and return a permit to send
+    /// it.
+    ///
+    /// This is the asynchronous counterpart of [`RateLimiter::acquire`]. The limiter does not
+    /// depend on an async runtime, so the delay is waited using the provided `sleep` function,
+    /// such as `tokio::time::sleep`. The [`Clock`] is only used to read the current time.
+    ///
+    /// ### Example
+    /// ```
+    /// # fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+    /// #     let mut fut = std::pin::pin!(fut);
+    /// #     let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+    /// #     loop {
+    /// #         if let std::task::Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+    /// #             return out;
+    /// #         }
+    /// #     }
+    /// # }
+    /// use std::time::Duration;
+    ///
+    /// use rsxiv::client::{FakeClock, RateLimiter};
+    ///
+    /// let limiter = RateLimiter::with_clock(FakeClock::new());
+    /// let sleep = |duration| {
+    ///     limiter.clock().advance(duration);
+    ///     std::future::ready(())
+    /// };
+    ///
+    /// # block_on(async {
+    /// for _ in 0..3 {
+    ///     let permit = limiter.acquire_async(sleep).await;
+    ///     // send the request
+    ///     drop(permit);
+    /// }
+    /// # });
+    /// assert_eq!(limiter.clock().elapsed(), Duration::from_secs(6));
+    /// ```
+    pub async fn acquire_async<F, S>(&self, mut sleep: S) -> Permit<'_, C>
+    where
+        S: FnMut(Duration) -> F,
+        F: Future<Output = ()>,
+    {
+        loop {
+            let wait = poll_fn(|cx| {
+                let mut state = self.lock();
+                if state.held {
+                    if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                        state.wakers.push(cx.waker().clone());
+                    }
+                    return Poll::Pending;
+                }
+                Poll::Ready(self.try_acquire(&mut state))
+            })
+            .await;
+
+            match wait {
+                Ok(permit) => return permit,
+                Err(wait) => sleep(wait).await,
+            }
+        }
+    }
+
+    /// Take the permit if the delay after the previous request has passed, or return the time
+    /// remaining. The permit must not be held.
+    fn try_acquire(&self, state: &mut LimiterState) -> Result<Permit<'_, C>, Duration> {
+        if let Some(next) = state.next {
+            let now = self.clock.now();
+            if next > now {
+                return Err(next - now);
+            }
+        }
+
+        state.held = true;
+        Ok(Permit {
+            limiter: self,
+            unavailable: false,
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LimiterState> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// The delay before the next request, after `failures` consecutive unavailable responses.
+    fn backoff(&self, failures: u32) -> Duration {
+        self.delay
+            .checked_mul(1 << failures.min(31))
+            .map_or(self.max_backoff, |delay| delay.min(self.max_backoff))
+            .max(self.delay)
+    }
+}
+
+/// Permission to send a single request, returned by [`RateLimiter::acquire`] and
+/// [`RateLimiter::acquire_async`].
+///
+/// No other permit can be acquired until this permit is dropped.
+#[derive(Debug)]
+pub struct Permit<'a, C: Clock> {
+    limiter: &'a RateLimiter<C>,
+    unavailable: bool,
+}
+
+impl<C: Clock> Permit<'_, C> {
+    /// Release the permit, recording that the server responded as unavailable (HTTP 503).
+    ///
+    /// The delay before the next request is increased exponentially until a request succeeds.
+    pub fn unavailable(mut self) {
+        self.unavailable = true;
+    }
+}
+
+impl<C: Clock> Drop for Permit<'_, C> {
+    fn drop(&mut self) {
+        let mut state = self.limiter.lock();
+        if self.unavailable {
+            state.failures = state.failures.saturating_add(1);
+        } else {
+            state.failures = 0;
+        }
+
+        let mut delay = self.limiter.backoff(state.failures);
+        if !self.limiter.jitter.is_zero() {
+            // xorshift64
+            state.rng ^= state.rng << 13;
+            state.rng ^= state.rng >> 7;
+            state.rng ^= state.rng << 17;
+            delay += self
+                .limiter
+                .jitter
+                .mul_f64((state.rng >> 11) as f64 / (1u64 << 53) as f64);
+        }
+        state.next = Some(self.limiter.clock.now() + delay);
+        state.held = false;
+
+        let wakers = std::mem::take(&mut state.wakers);
+        drop(state);
+        self.limiter.released.notify_all();
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+/// A [`Transport`] which sends every request through a [`RateLimiter`].
+///
+/// If the inner transport reports that the server is [unavailable](Transport::is_unavailable),
+/// the request is retried after the backoff delay, up to the configured number of
+/// [retries](RateLimited::retries).
+///
+/// ### Example
+/// ```
+/// use std::sync::Arc;
+///
+/// use rsxiv::client::{Client, MemoryTransport, RateLimited, RateLimiter};
+///
+/// // share one limiter between every client in the process
+/// let limiter = Arc::new(RateLimiter::new());
+///
+/// let client = Client::new(RateLimited::with_limiter(
+///     MemoryTransport::new(),
+///     limiter.clone(),
+/// ));
+/// ```
+#[derive(Debug)]
+pub struct RateLimited<T, C = SystemClock> {
+    transport: T,
+    limiter: Arc<RateLimiter<C>>,
+    retries: u32,
+}
+
+impl<T: Transport> RateLimited<T> {
+    /// Wrap the transport with a new rate limiter using the default configuration.
+    pub fn new(transport: T) -> Self {
+        Self::with_limiter(transport, Arc::new(RateLimiter::new()))
+    }
+}
+
+impl<T: Transport, C: Clock> RateLimited<T, C> {
+    /// The default number of times an unavailable request is retried.
+    pub const DEFAULT_RETRIES: u32 = 3;
+
+    /// Wrap the transport with the provided rate limiter, which may be shared with other
+    /// transports.
+    pub fn with_limiter(transport: T, limiter: Arc<RateLimiter<C>>) -> Self {
+        Self {
+            transport,
+            limiter,
+            retries: Self::DEFAULT_RETRIES,
+        }
+    }
+
+    /// Set the number of times a request is retried if the server is unavailable.
+    pub fn retries(&mut self, retries: u32) -> &mut Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Returns a reference to the rate limiter.
+    pub fn limiter(&self) -> &Arc<RateLimiter<C>> {
+        &self.limiter
+    }
+
+    /// Returns a reference to the underlying transport.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Returns the underlying transport.
+    pub fn into_transport(self) -> T {
+        self.transport
+    }
+}
+
+impl<T: Transport, C: Clock> Transport for RateLimited<T, C> {
+    type Error = T::Error;
+
+    fn send(&self, request: &Request) -> Result<Vec<u8>, Self::Error> {
+        let mut attempt = 0;
+        loop {
+            let permit = self.limiter.acquire();
+            match self.transport.send(request) {
+                Err(err) if T::is_unavailable(&err) => {
+                    permit.unavailable();
+                    if attempt == self.retries {
+                        return Err(err);
+                    }
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn is_unavailable(error: &Self::Error) -> bool {
+        T::is_unavailable(error)
+    }
+}
@@ -0,0 +1,406 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, ready},
+    time::Duration,
+};
+
+use futures_core::Stream;
+
+use super::{
+    ClientError, Clock, EMPTY_PAGE_RETRIES, MemoryTransport, RateLimiter, Request, SystemClock,
+    Transport,
+};
+use crate::{
+    query::{MAX_RESULTS, MAX_START, Query},
+    response::{Entry, OwnedEntry, Response, parse},
+};
+
+/// An asynchronous transport which sends [`Request`]s and returns the response body.
+///
+/// This is the asynchronous counterpart of the [`Transport`] trait. Implement this trait to use
+/// any asynchronous HTTP library with an [`AsyncClient`].
+pub trait AsyncTransport {
+    /// The error returned if the request fails.
+    type Error;
+
+    /// Send the request, returning the body of the response.
+    fn send(&self, request: Request) -> impl Future<Output = Result<Vec<u8>, Self::Error>> + Send;
+
+    /// Returns if the error indicates that the server is temporarily unavailable, such as an HTTP
+    /// 503 response, so that the request may be retried later.
+    ///
+    /// This is used by [`AsyncRateLimited`] to back off. The default implementation returns
+    /// `false`.
+    fn is_unavailable(error: &Self::Error) -> bool {
+        let _ = error;
+        false
+    }
+}
+
+impl AsyncTransport for MemoryTransport {
+    type Error = <Self as Transport>::Error;
+
+    fn send(&self, request: Request) -> impl Future<Output = Result<Vec<u8>, Self::Error>> + Send {
+        std::future::ready(Transport::send(self, &request))
+    }
+
+    fn is_unavailable(error: &Self::Error) -> bool {
+        <Self as Transport>::is_unavailable(error)
+    }
+}
+
+/// An [`AsyncTransport`] which sends every request through a [`RateLimiter`].
+///
+/// This is the asynchronous counterpart of [`RateLimited`](super::RateLimited). Permits are
+/// acquired with [`RateLimiter::acquire_async`], and the delay is waited using the provided
+/// `sleep` function, such as `tokio::time::sleep`, so that the thread is not blocked.
+///
+/// If the inner transport reports that the server is
+/// [unavailable](AsyncTransport::is_unavailable), the request is retried after the backoff delay,
+/// up to the configured number of [retries](AsyncRateLimited::retries).
+///
+/// ### Example
+/// ```
+/// use std::sync::Arc;
+///
+/// use rsxiv::client::{AsyncClient, AsyncRateLimited, MemoryTransport, RateLimiter};
+///
+/// // share one limiter between every client in the process
+/// let limiter = Arc::new(RateLimiter::new());
+///
+/// # let sleep = |_| std::future::ready(());
+/// // use the sleep function of your async runtime, like `tokio::time::sleep`
+/// let client = AsyncClient::new(AsyncRateLimited::with_limiter(
+///     MemoryTransport::new(),
+///     limiter.clone(),
+///     sleep,
+/// ));
+/// ```
+#[derive(Debug)]
+pub struct AsyncRateLimited<T, S, C = SystemClock> {
+    transport: T,
+    limiter: Arc<RateLimiter<C>>,
+    sleep: S,
+    retries: u32,
+}
+
+impl<T, S, F> AsyncRateLimited<T, S>
+where
+    T: AsyncTransport,
+    S: Fn(Duration) -> F,
+    F: Future<Output = ()>,
+{
+    /// Wrap the transport with a new rate limiter using the default configuration, which waits
+    /// using the `sleep` function.
+    pub fn new(transport: T, sleep: S) -> Self {
+        Self::with_limiter(transport, Arc::new(RateLimiter::new()), sleep)
+    }
+}
+
+impl<T, S, F, C> AsyncRateLimited<T, S, C>
+where
+    T: AsyncTransport,
+    S: Fn(Duration) -> F,
+    F: Future<Output = ()>,
+    C: Clock,
+{
+    /// The default number of times an unavailable request is retried.
+    pub const DEFAULT_RETRIES: u32 = 3;
+
+    /// Wrap the transport with the provided rate limiter, which may be shared with other
+    /// transports, and which waits using the `sleep` function.
+    pub fn with_limiter(transport: T, limiter: Arc<RateLimiter<C>>, sleep: S) -> Self {
+        Self {
+            transport,
+            limiter,
+            sleep,
+            retries: Self::DEFAULT_RETRIES,
+        }
+    }
+
+    /// Set the number of times a request is retried if the server is unavailable.
+    pub fn retries(&mut self, retries: u32) -> &mut Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Returns a reference to the rate limiter.
+    pub fn limiter(&self) -> &Arc<RateLimiter<C>> {
+        &self.limiter
+    }
+
+    /// Returns a reference to the underlying transport.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Returns the underlying transport.
+    pub fn into_transport(self) -> T {
+        self.transport
+    }
+}
+
+impl<T, S, F, C> AsyncTransport for AsyncRateLimited<T, S, C>
+where
+    T: AsyncTransport + Sync,
+    T::Error: Send,
+    S: Fn(Duration) -> F + Sync,
+    F: Future<Output = ()> + Send,
+    C: Clock + Send + Sync,
+{
+    type Error = T::Error;
+
+    async fn send(&self, request: Request) -> Result<Vec<u8>, Self::Error> {
+        let mut attempt = 0;
+        loop {
+            let permit = self.limiter.acquire_async(&self.sleep).await;
+            match self.transport.send(request.clone()).await {
+                Err(err) if T::is_unavailable(&err) => {
+                    permit.unavailable();
+                    if attempt == self.retries {
+                        return Err(err);
+                    }
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn is_unavailable(error: &Self::Error) -> bool {
+        T::is_unavailable(error)
+    }
+}
+
+/// An asynchronous client which sends [`Query`]s using an [`AsyncTransport`].
+///
+/// ### Example
+/// ```
+/// # fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+/// #     let mut fut = std::pin::pin!(fut);
+/// #     let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+/// #     loop {
+/// #         if let std::task::Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+/// #             return out;
+/// #         }
+/// #     }
+/// # }
+/// use std::pin::pin;
+///
+/// use futures_core::Stream;
+/// use rsxiv::{
+///     client::{AsyncClient, MemoryTransport},
+///     id::ArticleId,
+///     query::Query,
+/// };
+///
+/// let mut query = Query::new();
+/// query.id_list().push(&ArticleId::parse("2206.06921").unwrap());
+///
+/// // abridged arXiv response
+/// let xml = // r#"<feed xmlns...
+#[doc = include_str!("../response/tests/query_doc.txt")]
+/// let mut transport = MemoryTransport::new();
+/// transport.fallback(xml);
+/// let client = AsyncClient::new(transport);
+///
+/// # block_on(async {
+/// let mut entries = pin!(client.entries(&query));
+/// // use `StreamExt::next` from the `futures` crate
+/// while let Some(entry) = std::future::poll_fn(|cx| entries.as_mut().poll_next(cx)).await {
+///     assert_eq!(entry.unwrap().id, ArticleId::parse("2206.06921v3").unwrap());
+/// }
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct AsyncClient<T> {
+    transport: T,
+    post: bool,
+}
+
+impl<T: AsyncTransport> AsyncClient<T> {
+    /// Construct a new client using the provided transport.
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            post: false,
+        }
+    }
+
+    /// Send queries as `POST` requests instead of `GET` requests.
+    ///
+    /// This avoids length limits on the URL when requesting long identifier lists.
+    pub fn post(&mut self, post: bool) -> &mut Self {
+        self.post = post;
+        self
+    }
+
+    /// Returns a reference to the underlying transport.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Returns the underlying transport.
+    pub fn into_transport(self) -> T {
+        self.transport
+    }
+
+    /// Send the query and return the raw response body.
+    pub async fn fetch(&self, query: &Query) -> Result<Vec<u8>, ClientError<T::Error>> {
+        self.transport
+            .send(Request::new(query, self.post))
+            .await
+            .map_err(ClientError::Transport)
+    }
+
+    /// Send the query and parse the response.
+    pub async fn query(
+        &self,
+        query: &Query,
+    ) -> Result<Response<Vec<OwnedEntry>>, ClientError<T::Error>> {
+        let body = self.fetch(query).await?;
+        Ok(parse(&body)?.into_owned())
+    }
+
+    /// Returns a stream of every entry matching the query, requesting successive pages as
+    /// required.
+    ///
+    /// The first page starts at the `start` of the [pagination](Query::paginate) of the query, and
+    /// each page requests `max_results` entries. If the query is not paginated, the first page
+    /// starts at `0` and each page requests `2000` entries, the largest page size permitted by the
+    /// arXiv API.
+    ///
+    /// The stream ends when every result has been returned, or when the arXiv API does not permit
+    /// a further page. The arXiv API occasionally returns an empty page before the end of the
+    /// results, in which case the page is requested again, and [`ClientError::EmptyPage`] is
+    /// returned if the page is still empty after a few attempts. Every page, including a repeated
+    /// page, is sent through the transport, so wrap the transport in an [`AsyncRateLimited`] to
+    /// throttle the requests. If a page cannot be retrieved or
+    /// parsed, the error is returned and the stream ends; the stream can be resumed from
+    /// [`EntryStream::next_start`] with a new query.
+    pub fn entries(&self, query: &Query) -> EntryStream<'_, T> {
+        let (start, page_size) = match query.pagination() {
+            Some((start, max_results)) if max_results > 0 => (start, max_results),
+            Some((start, _)) => (start, MAX_RESULTS),
+            None => (0, MAX_RESULTS),
+        };
+
+        EntryStream {
+            client: self,
+            query: query.clone(),
+            page_size,
+            next_start: u64::from(start),
+            buffered: VecDeque::new(),
+            pending: None,
+            empty_pages: 0,
+            done: false,
+        }
+    }
+}
+
+/// The future returned by [`AsyncTransport::send`].
+type PendingPage<'a, E> = Pin<Box<dyn Future<Output = Result<Vec<u8>, E>> + Send + 'a>>;
+
+/// A [`Stream`] of the entries matching a query, constructed by [`AsyncClient::entries`].
+pub struct EntryStream<'a, T: AsyncTransport> {
+    client: &'a AsyncClient<T>,
+    query: Query,
+    page_size: u16,
+    next_start: u64,
+    buffered: VecDeque<OwnedEntry>,
+    pending: Option<PendingPage<'a, T::Error>>,
+    empty_pages: u32,
+    done: bool,
+}
+
+impl<T: AsyncTransport> EntryStream<'_, T> {
+    /// The index of the first result which has not yet been requested.
+    ///
+    /// If the stream ended early because of an error, a new query with this `start` resumes the
+    /// stream.
+    pub fn next_start(&self) -> u64 {
+        self.next_start
+    }
+
+    /// The request for the next page, or `None` if no further page can be requested.
+    fn request_page(&self) -> Option<Request> {
+        let start = u16::try_from(self.next_start)
+            .ok()
+            .filter(|start| *start <= MAX_START)?;
+        let mut query = self.query.clone();
+        query.paginate(start, self.page_size)?;
+        Some(Request::new(&query, self.client.post))
+    }
+
+    /// Update the stream with the response to a page request, returning an error if the response
+    /// is invalid.
+    fn receive_page(&mut self, body: &[u8]) -> Result<(), ClientError<T::Error>> {
+        let response = parse(body)?;
+        let count = response.entries.len() as u64;
+        let pagination = response.pagination;
+
+        if count == 0 && pagination.start_index < pagination.total_results {
+            // request the same page again
+            self.empty_pages += 1;
+            if self.empty_pages > EMPTY_PAGE_RETRIES {
+                return Err(ClientError::EmptyPage(self.next_start));
+            }
+            return Ok(());
+        }
+
+        self.empty_pages = 0;
+        self.buffered
+            .extend(response.entries.into_iter().map(Entry::into_owned));
+        self.next_start = pagination.start_index + count;
+        if count == 0 || self.next_start >= pagination.total_results {
+            self.done = true;
+        }
+        Ok(())
+    }
+}
+
+impl<T: AsyncTransport> Stream for EntryStream<'_, T> {
+    type Item = Result<OwnedEntry, ClientError<T::Error>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(entry) = this.buffered.pop_front() {
+                return Poll::Ready(Some(Ok(entry)));
+            }
+
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            let pending = match &mut this.pending {
+                Some(pending) => pending,
+                None => {
+                    let Some(request) = this.request_page() else {
+                        this.done = true;
+                        return Poll::Ready(None);
+                    };
+                    this.pending
+                        .insert(Box::pin(this.client.transport.send(request)))
+                }
+            };
+
+            let result = ready!(pending.as_mut().poll(cx));
+            this.pending = None;
+
+            let error = match result {
+                Ok(body) => match this.receive_page(&body) {
+                    Ok(()) => continue,
+                    Err(err) => err,
+                },
+                Err(err) => ClientError::Transport(err),
+            };
+            this.done = true;
+            return Poll::Ready(Some(Err(error)));
+        }
+    }
+}
@@ -0,0 +1,597 @@
+use super::*;
+use crate::query::Field;
+
+const QUERY_XML: &[u8] = include_bytes!("../response/tests/query.xml");
+const EMPTY_XML: &[u8] = include_bytes!("../response/tests/query_empty.xml");
+
+fn query(title: &str) -> Query {
+    let mut query = Query::new();
+    query.search_query().init(Field::ti(title).unwrap());
+    query
+}
+
+#[test]
+fn test_memory_client() {
+    let mut transport = MemoryTransport::new();
+    transport
+        .insert(&query("proton"), QUERY_XML)
+        .insert(&query("electron"), EMPTY_XML);
+
+    let mut client = Client::new(&transport);
+    let mut buffer = Vec::new();
+
+    let response = client.query(&query("proton"), &mut buffer).unwrap();
+    assert_eq!(response.entries.len(), 10);
+
+    // `POST` requests are matched against the equivalent `GET` request
+    client.post(true);
+    let response = client.query(&query("electron"), &mut buffer).unwrap();
+    assert!(response.entries.is_empty());
+
+    assert!(matches!(
+        client.fetch(&query("neutron")),
+        Err(ClientError::Transport(MissingFixture(Request::Post { .. })))
+    ));
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[0],
+        Request::Get {
+            url: query("proton").url()
+        }
+    );
+    assert_eq!(
+        requests[1],
+        Request::Post {
+            url: query("electron").endpoint(),
+            body: query("electron").form_body(),
+        }
+    );
+    assert_eq!(requests[1].get_url(), query("electron").url());
+}
+
+#[test]
+fn test_client_errors() {
+    let mut transport = MemoryTransport::new();
+    transport.fallback("not xml");
+
+    let client = Client::new(transport);
+    let err = client.query(&query("proton"), &mut Vec::new()).unwrap_err();
+    assert!(matches!(err, ClientError::Response(_)));
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_client_deserialize() {
+    #[derive(serde::Deserialize)]
+    struct Entry {
+        id: crate::ArticleId,
+    }
+
+    let mut transport = MemoryTransport::new();
+    transport.insert(&query("proton"), QUERY_XML);
+
+    let response = Client::new(transport)
+        .deserialize::<Vec<Entry>>(&query("proton"))
+        .unwrap();
+    assert_eq!(response.entries.len(), 10);
+    assert_eq!(
+        response.entries[0].id,
+        crate::ArticleId::parse("nucl-ex/0408020v1").unwrap()
+    );
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_entry_stream() {
+    use std::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    use futures_core::Stream;
+
+    /// Collect the stream, assuming every future is immediately ready.
+    fn collect<S: Stream>(stream: S) -> Vec<S::Item> {
+        let mut stream = pin!(stream);
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut items = Vec::new();
+        loop {
+            match stream.as_mut().poll_next(&mut cx) {
+                Poll::Ready(Some(item)) => items.push(item),
+                Poll::Ready(None) => return items,
+                Poll::Pending => panic!("stream is pending"),
+            }
+        }
+    }
+
+    let xml = std::str::from_utf8(QUERY_XML).unwrap();
+    let page = |start: u16, total: &str| {
+        xml.replace(
+            "<opensearch:totalResults>7432<",
+            &format!("<opensearch:totalResults>{total}<"),
+        )
+        .replace(
+            "<opensearch:startIndex>0<",
+            &format!("<opensearch:startIndex>{start}<"),
+        )
+    };
+    let paged = |start| {
+        let mut query = query("proton");
+        query.paginate(start, 10).unwrap();
+        query
+    };
+
+    let mut transport = MemoryTransport::new();
+    transport
+        .insert(&paged(0), page(0, "25"))
+        .insert(&paged(10), page(10, "25"))
+        .insert(&paged(20), page(20, "25"));
+    let client = AsyncClient::new(transport);
+
+    // the final page contains 10 entries, but the total is only 25
+    let entries = collect(client.entries(&paged(0)));
+    assert_eq!(entries.len(), 30);
+    assert!(entries.iter().all(Result::is_ok));
+    assert_eq!(client.transport().requests().len(), 3);
+
+    // an error is returned after the entries already received, and ends the stream
+    let mut transport = MemoryTransport::new();
+    transport.insert(&paged(0), page(0, "25"));
+    let client = AsyncClient::new(transport);
+    let mut stream = client.entries(&paged(0));
+    let items = collect(&mut stream);
+    assert_eq!(items.len(), 11);
+    assert!(items[..10].iter().all(Result::is_ok));
+    assert!(matches!(items[10], Err(ClientError::Transport(_))));
+    assert_eq!(stream.next_start(), 10);
+
+    // an empty page before the end of the results is requested again, and then returns an error
+    let empty = std::str::from_utf8(EMPTY_XML)
+        .unwrap()
+        .replace(
+            "<opensearch:totalResults>0<",
+            "<opensearch:totalResults>25<",
+        )
+        .replace("<opensearch:startIndex>0<", "<opensearch:startIndex>10<");
+    let mut transport = MemoryTransport::new();
+    transport
+        .insert(&paged(0), page(0, "25"))
+        .insert(&paged(10), empty.clone());
+    let client = AsyncClient::new(transport);
+    let mut stream = client.entries(&paged(0));
+    let items = collect(&mut stream);
+    assert_eq!(items.len(), 11);
+    assert!(matches!(items[10], Err(ClientError::EmptyPage(10))));
+    assert_eq!(stream.next_start(), 10);
+    assert_eq!(client.transport().requests().len(), 5);
+
+    // the repeated pages are throttled by a rate-limited transport
+    let mut transport = MemoryTransport::new();
+    transport
+        .insert(&paged(0), page(0, "25"))
+        .insert(&paged(10), empty);
+    let limiter = std::sync::Arc::new(RateLimiter::with_clock(FakeClock::new()));
+    let sleep = {
+        let limiter = limiter.clone();
+        move |duration| {
+            limiter.clock().advance(duration);
+            std::future::ready(())
+        }
+    };
+    let client = AsyncClient::new(AsyncRateLimited::with_limiter(
+        transport,
+        limiter.clone(),
+        sleep,
+    ));
+    let items = collect(client.entries(&paged(0)));
+    assert!(matches!(items[10], Err(ClientError::EmptyPage(10))));
+    assert_eq!(client.transport().transport().requests().len(), 5);
+    assert_eq!(
+        limiter.clock().elapsed(),
+        std::time::Duration::from_secs(4 * 3)
+    );
+
+    // single requests
+    let mut cx = Context::from_waker(Waker::noop());
+    let response = match pin!(client.query(&paged(0))).poll(&mut cx) {
+        Poll::Ready(response) => response.unwrap(),
+        Poll::Pending => panic!("future is pending"),
+    };
+    assert_eq!(response.entries.len(), 10);
+}
+
+#[test]
+fn test_rate_limiter() {
+    use std::{cell::Cell, sync::Arc, time::Duration};
+
+    /// A transport which is unavailable for a number of requests.
+    struct Flaky(Cell<u32>);
+
+    impl Transport for Flaky {
+        type Error = u32;
+
+        fn send(&self, _: &Request) -> Result<Vec<u8>, Self::Error> {
+            match self.0.get() {
+                0 => Ok(EMPTY_XML.to_vec()),
+                n => {
+                    self.0.set(n - 1);
+                    Err(503)
+                }
+            }
+        }
+
+        fn is_unavailable(error: &Self::Error) -> bool {
+            *error == 503
+        }
+    }
+
+    let secs = Duration::from_secs;
+    let limiter = Arc::new(RateLimiter::with_clock(FakeClock::new()));
+    let clock = || limiter.clock().elapsed();
+
+    // the first request is not delayed, and time spent elsewhere counts towards the delay
+    let client = Client::new(RateLimited::with_limiter(
+        MemoryTransport::new(),
+        limiter.clone(),
+    ));
+    client.fetch(&query("proton")).unwrap_err();
+    assert_eq!(clock(), secs(0));
+    limiter.clock().advance(secs(1));
+    client.fetch(&query("proton")).unwrap_err();
+    assert_eq!(clock(), secs(3));
+
+    // unavailable requests are retried with exponential backoff
+    let mut transport = RateLimited::with_limiter(Flaky(Cell::new(2)), limiter.clone());
+    transport.retries(2);
+    let client = Client::new(transport);
+    client.fetch(&query("proton")).unwrap();
+    assert_eq!(clock(), secs(3 + 3 + 6 + 12));
+    assert_eq!(limiter.failures(), 0);
+
+    // the error is returned once the retries are exhausted
+    let mut transport = RateLimited::with_limiter(Flaky(Cell::new(5)), limiter.clone());
+    transport.retries(1);
+    let client = Client::new(transport);
+    assert!(matches!(
+        client.fetch(&query("proton")),
+        Err(ClientError::Transport(503))
+    ));
+    assert_eq!(limiter.failures(), 2);
+    assert_eq!(clock(), secs(24 + 3 + 6));
+
+    // the backoff is capped
+    let mut limiter = RateLimiter::with_clock(FakeClock::new());
+    limiter.max_backoff(secs(5));
+    for _ in 0..3 {
+        limiter.acquire().unavailable();
+    }
+    drop(limiter.acquire());
+    assert_eq!(limiter.clock().elapsed(), secs(5 + 5 + 5));
+
+    // jitter is bounded and deterministic for a fixed seed
+    let elapsed = |seed| {
+        let mut limiter = RateLimiter::with_clock(FakeClock::new());
+        limiter.delay(secs(1)).jitter(secs(1)).seed(seed);
+        for _ in 0..11 {
+            drop(limiter.acquire());
+        }
+        limiter.clock().elapsed()
+    };
+    assert!((secs(10)..secs(20)).contains(&elapsed(7)));
+    assert_eq!(elapsed(7), elapsed(7));
+    assert_ne!(elapsed(7), elapsed(8));
+
+    // asynchronous permits wait for the held permit without blocking, and then for the delay
+    {
+        use std::{
+            future::Future,
+            pin::pin,
+            task::{Context, Poll, Waker},
+        };
+
+        let limiter = RateLimiter::with_clock(FakeClock::new());
+        let sleep = |duration| {
+            limiter.clock().advance(duration);
+            std::future::ready(())
+        };
+        let mut cx = Context::from_waker(Waker::noop());
+
+        let permit = limiter.acquire();
+        let mut acquire = pin!(limiter.acquire_async(sleep));
+        assert!(acquire.as_mut().poll(&mut cx).is_pending());
+        permit.unavailable();
+        let Poll::Ready(permit) = acquire.as_mut().poll(&mut cx) else {
+            panic!("permit was not released");
+        };
+        assert_eq!(limiter.clock().elapsed(), secs(6));
+        assert_eq!(limiter.failures(), 1);
+        drop(permit);
+    }
+}
+
+#[test]
+fn test_cache() {
+    use std::time::Duration;
+
+    let dir = std::env::temp_dir().join(format!("rsxiv-test-cache-{}", std::process::id()));
+    let mut transport = MemoryTransport::new();
+    transport
+        .insert(&query("proton"), QUERY_XML)
+        .insert(&query("electron"), "<html>unavailable</html>")
+        .insert(
+            &query("neutron"),
+            std::str::from_utf8(QUERY_XML).unwrap().replace(
+                "http://arxiv.org/abs/1606.02159v1",
+                "http://arxiv.org/api/errors#incorrect_id_format",
+            ),
+        );
+    let mut cached = Cached::with_clock(transport, &dir, FakeClock::new());
+    let sent = |cached: &Cached<MemoryTransport, FakeClock>| cached.transport().requests().len();
+
+    // the second request is served from the cache, including as a `POST` request
+    let mut client = Client::new(&cached);
+    assert_eq!(client.fetch(&query("proton")).unwrap(), QUERY_XML);
+    client.post(true);
+    assert_eq!(client.fetch(&query("proton")).unwrap(), QUERY_XML);
+    assert_eq!(sent(&cached), 1);
+
+    let response = cached.get(&query("proton")).unwrap().unwrap();
+    assert_eq!(response.body, QUERY_XML);
+    assert_eq!(
+        response.updated,
+        parse(QUERY_XML).unwrap().updated,
+        "feed timestamp is stored"
+    );
+    assert!(response.stored <= cached.clock().system_time());
+
+    // the stored file can be parsed directly
+    let path = cached.path(&Request::new(&query("proton"), false));
+    let stored = std::fs::read(&path).unwrap();
+    assert_eq!(parse(&stored).unwrap().entries.len(), 10);
+
+    // a file stored for a different URL is not served
+    let other = cached.path(&Request::new(&query("positron"), false));
+    std::fs::copy(&path, &other).unwrap();
+    assert!(cached.get(&query("positron")).unwrap().is_none());
+    std::fs::remove_file(other).unwrap();
+
+    // invalid responses are returned but not cached
+    let client = Client::new(&cached);
+    client.fetch(&query("electron")).unwrap();
+    assert!(cached.get(&query("electron")).unwrap().is_none());
+    assert!(matches!(
+        client.query(&query("electron"), &mut Vec::new()),
+        Err(ClientError::Response(_))
+    ));
+
+    // arXiv API errors are not cached, even after other entries
+    let client = Client::new(&cached);
+    client.fetch(&query("neutron")).unwrap();
+    assert!(cached.get(&query("neutron")).unwrap().is_none());
+    assert_eq!(sent(&cached), 4);
+
+    // no temporary files are left behind
+    assert!(
+        std::fs::read_dir(&dir)
+            .unwrap()
+            .all(|entry| entry.unwrap().path().extension().unwrap() == "xml")
+    );
+
+    // expired responses are requested again
+    cached.ttl(Some(Duration::from_secs(3600)));
+    cached.clock().advance(Duration::from_secs(3599));
+    Client::new(&cached).fetch(&query("proton")).unwrap();
+    assert_eq!(sent(&cached), 4);
+    cached.clock().advance(Duration::from_secs(1));
+    Client::new(&cached).fetch(&query("proton")).unwrap();
+    assert_eq!(sent(&cached), 5);
+
+    // refreshing always sends the request
+    cached.ttl(None).mode(CacheMode::Refresh);
+    Client::new(&cached).fetch(&query("proton")).unwrap();
+    assert_eq!(sent(&cached), 6);
+
+    // offline mode serves expired responses and never sends a request
+    cached
+        .ttl(Some(Duration::ZERO))
+        .mode(CacheMode::OfflineOnly);
+    let client = Client::new(&cached);
+    let mut buffer = Vec::new();
+    assert_eq!(
+        client
+            .query(&query("proton"), &mut buffer)
+            .unwrap()
+            .entries
+            .len(),
+        10
+    );
+    assert!(matches!(
+        client.fetch(&query("muon")),
+        Err(ClientError::Transport(CacheError::Missing(_)))
+    ));
+    cached.remove(&query("proton")).unwrap();
+    assert!(Client::new(&cached).fetch(&query("proton")).is_err());
+    assert_eq!(sent(&cached), 6);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_harvester() {
+    use chrono::NaiveDateTime;
+
+    use crate::{id::ArticleId, query::Category};
+
+    fn feed(total: u64, start: u64, entries: &[(&str, &str)]) -> String {
+        let mut xml = format!(
+            r#"<?xml version='1.0' encoding='UTF-8'?>
+<feed xmlns:opensearch="http://a9.com/-/spec/opensearch/1.1/" xmlns:arxiv="http://arxiv.org/schemas/atom" xmlns="http://www.w3.org/2005/Atom">
+  <updated>2024-01-06T00:00:00Z</updated>
+  <opensearch:itemsPerPage>2</opensearch:itemsPerPage>
+  <opensearch:totalResults>{total}</opensearch:totalResults>
+  <opensearch:startIndex>{start}</opensearch:startIndex>"#
+        );
+        for (id, updated) in entries {
+            xml.push_str(&format!(
+                r#"
+  <entry>
+    <id>http://arxiv.org/abs/{id}</id>
+    <title>Title</title>
+    <updated>{updated}</updated>
+    <summary>Summary</summary>
+    <category term="math.CA" scheme="http://arxiv.org/schemas/atom"/>
+    <published>2024-01-01T00:00:00Z</published>
+    <arxiv:primary_category term="math.CA"/>
+    <author>
+      <name>A. Author</name>
+    </author>
+  </entry>"#
+            ));
+        }
+        xml.push_str("\n</feed>");
+        xml
+    }
+
+    let time = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap();
+    let id = |s: &str| ArticleId::parse(s).unwrap();
+    let categories = [Category::from_id("math.CA").unwrap()];
+    let page = |since: &str, start| {
+        let mut query = Query::category_updates(categories, time(since)).unwrap();
+        query.paginate(start, 2).unwrap();
+        query
+    };
+
+    let mut transport = MemoryTransport::new();
+    transport
+        .insert(
+            &page("2024-01-01T00:00:00", 0),
+            feed(
+                5,
+                0,
+                &[
+                    ("2401.00001v1", "2024-01-02T10:00:00Z"),
+                    ("2401.00002v2", "2024-01-03T00:00:00Z"),
+                ],
+            ),
+        )
+        // the results shifted between requests, so an entry is repeated
+        .insert(
+            &page("2024-01-01T00:00:00", 2),
+            feed(
+                5,
+                2,
+                &[
+                    ("2401.00002v2", "2024-01-03T00:00:00Z"),
+                    ("2401.00003v1", "2024-01-04T12:00:00Z"),
+                ],
+            ),
+        )
+        .insert(
+            &page("2024-01-01T00:00:00", 4),
+            feed(5, 4, &[("2401.00004v1", "2024-01-04T12:00:00Z")]),
+        )
+        .insert(
+            &page("2024-01-04T12:00:00", 0),
+            feed(
+                3,
+                0,
+                &[
+                    ("2401.00003v1", "2024-01-04T12:00:00Z"),
+                    ("2401.00004v1", "2024-01-04T12:00:00Z"),
+                ],
+            ),
+        )
+        .insert(
+            &page("2024-01-04T12:00:00", 2),
+            feed(3, 2, &[("2401.00001v2", "2024-01-05T08:30:00Z")]),
+        );
+
+    let mut harvester = Harvester::new(Client::new(transport), categories);
+    assert!(harvester.page_size(0).is_none());
+    harvester.page_size(2).unwrap();
+
+    let mut checkpoint = Checkpoint::new(time("2024-01-01T00:00:00"));
+    let mut ids = Vec::new();
+    let count = harvester
+        .harvest(&mut checkpoint, |entry| ids.push(entry.id))
+        .unwrap();
+    assert_eq!(count, 4);
+    assert_eq!(
+        ids,
+        [
+            "2401.00001v1",
+            "2401.00002v2",
+            "2401.00003v1",
+            "2401.00004v1"
+        ]
+        .map(id)
+    );
+    assert_eq!(
+        checkpoint,
+        Checkpoint {
+            updated: time("2024-01-04T12:00:00"),
+            seen: vec![id("2401.00003"), id("2401.00004")],
+        }
+    );
+
+    // an empty page before the end of the results is requested again, and then returns an error
+    // without advancing the checkpoint past the missing entries
+    let mut transport = MemoryTransport::new();
+    transport
+        .insert(
+            &page("2024-01-01T00:00:00", 0),
+            feed(
+                5,
+                0,
+                &[
+                    ("2401.00001v1", "2024-01-02T10:00:00Z"),
+                    ("2401.00002v2", "2024-01-03T00:00:00Z"),
+                ],
+            ),
+        )
+        .insert(&page("2024-01-01T00:00:00", 2), feed(5, 2, &[]));
+    let mut empty = Harvester::new(Client::new(transport), categories);
+    empty.page_size(2).unwrap();
+    let mut partial = Checkpoint::new(time("2024-01-01T00:00:00"));
+    assert!(matches!(
+        empty.harvest(&mut partial, |_| {}),
+        Err(ClientError::EmptyPage(2))
+    ));
+    assert_eq!(partial.updated, time("2024-01-03T00:00:00"));
+    assert_eq!(empty.client().transport().requests().len(), 5);
+
+    // the checkpoint survives a round trip through a file
+    let path = std::env::temp_dir().join(format!("rsxiv-test-checkpoint-{}", std::process::id()));
+    checkpoint.save(&path).unwrap();
+    assert!(
+        std::fs::read_dir(std::env::temp_dir())
+            .unwrap()
+            .all(|entry| !entry
+                .unwrap()
+                .file_name()
+                .to_string_lossy()
+                .starts_with(&format!("{}.", path.file_name().unwrap().to_string_lossy()))),
+        "temporary file is removed"
+    );
+    let mut checkpoint = Checkpoint::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(Checkpoint::parse("2024-01-04T12:00:00\nnot-an-id").is_none());
+
+    // only the entries updated since the checkpoint are emitted
+    let mut ids = Vec::new();
+    harvester
+        .harvest(&mut checkpoint, |entry| ids.push(entry.id))
+        .unwrap();
+    assert_eq!(ids, [id("2401.00001v2")]);
+    assert_eq!(checkpoint.updated, time("2024-01-05T08:30:00"));
+    assert_eq!(harvester.client().transport().requests().len(), 5);
+
+    // a failed request leaves a checkpoint from which the harvest can resume
+    let mut checkpoint = Checkpoint::new(time("2023-12-31T00:00:00"));
+    assert!(harvester.harvest(&mut checkpoint, |_| {}).is_err());
+    assert_eq!(checkpoint, Checkpoint::new(time("2023-12-31T00:00:00")));
+}
@@ -0,0 +1,91 @@
+use super::{Request, Transport};
+
+/// A [`Transport`] backed by a [`ureq::Agent`].
+///
+/// ### Example
+/// ```no_run
+/// use rsxiv::{
+///     client::{Client, UreqTransport},
+///     query::{Field, Query},
+/// };
+///
+/// let mut query = Query::new();
+/// query.search_query().init(Field::ti("proton").unwrap());
+///
+/// let client = Client::new(UreqTransport::new());
+/// let mut buffer = Vec::new();
+/// let response = client.query(&query, &mut buffer).unwrap();
+/// for entry in response.entries {
+///     println!("{}", entry.title);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct UreqTransport {
+    agent: ureq::Agent,
+    body_limit: u64,
+}
+
+impl Default for UreqTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UreqTransport {
+    /// The default maximum size of a response body, in bytes.
+    ///
+    /// This is large enough for a page containing the maximum of 2000 entries.
+    pub const DEFAULT_BODY_LIMIT: u64 = 64 * 1024 * 1024;
+
+    /// Construct a new transport using an agent with the default configuration.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_agent(ureq::Agent::new_with_defaults())
+    }
+
+    /// Construct a new transport using the provided agent.
+    #[must_use]
+    pub fn with_agent(agent: ureq::Agent) -> Self {
+        Self {
+            agent,
+            body_limit: Self::DEFAULT_BODY_LIMIT,
+        }
+    }
+
+    /// Set the maximum size of a response body, in bytes. A larger response results in an
+    /// error. Defaults to [`DEFAULT_BODY_LIMIT`](Self::DEFAULT_BODY_LIMIT).
+    pub fn body_limit(&mut self, body_limit: u64) -> &mut Self {
+        self.body_limit = body_limit;
+        self
+    }
+}
+
+impl From<ureq::Agent> for UreqTransport {
+    fn from(agent: ureq::Agent) -> Self {
+        Self::with_agent(agent)
+    }
+}
+
+impl Transport for UreqTransport {
+    type Error = ureq::Error;
+
+    fn send(&self, request: &Request) -> Result<Vec<u8>, Self::Error> {
+        let response = match request {
+            Request::Get { url } => self.agent.get(url.as_str()).call()?,
+            Request::Post { url, body } => self
+                .agent
+                .post(url.as_str())
+                .content_type("application/x-www-form-urlencoded")
+                .send(body.as_str())?,
+        };
+        response
+            .into_body()
+            .with_config()
+            .limit(self.body_limit)
+            .read_to_vec()
+    }
+
+    fn is_unavailable(error: &Self::Error) -> bool {
+        matches!(error, ureq::Error::StatusCode(503))
+    }
+}
@@ -38,10 +38,17 @@
 //! - `journal_ref`: `Option<Str>`,
 //! - `primary_category`: `Option<Str>`,
 //! - `category`: `Seq<Str>`,
+//! - `links`: `Links`,
 //!
 //! ### `EntryNoId`
 //! Identical to `Entry`, but without the `id` field.
 //!
+//! ### `Links`
+//! The links in the entry. A `Map` with the following explicit keys and corresponding values:
+//! - `abs`: `Option<Str>`, the URL of the abstract page
+//! - `pdf`: `Option<Str>`, the URL of the PDF
+//! - `doi`: `Option<Str>`, the URL of the resolved DOI
+//!
 //! ### `ArticleId`
 //! A representation of a arXiv identifier. Can be deserialized as:
 //!
@@ -75,6 +82,14 @@
 //! Any serde string type, like `str` or `string` or `borrowed_str`. Whenever possible, this
 //! borrows from the input data, but this is not always possible because of escape sequences.
 //!
+//! ## Errors
+//! An error in an entry is returned as a [`ResponseError::Context`] with the index and identifier
+//! of the entry, and the path to the field in the data model above, such as `authors[1].name`.
+//! Use [`ResponseError::without_context`] to match on the underlying error.
+//!
+//! [`ResponseError::Context`]: crate::response::ResponseError::Context
+//! [`ResponseError::without_context`]: crate::response::ResponseError::without_context
+//!
 //! ## Examples
 //! ### Basic usage example
 //! A basic example is as follows.
@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, vec};
 
 use serde::{
     de::{
@@ -8,16 +8,21 @@ use serde::{
     forward_to_deserialize_any,
 };
 
-use crate::response::{ResponseError, ResponseReader, Term};
+use crate::response::{Links, RawAuthor, RawEntry, ResponseError, ResponseReader, Term};
 
 /// A deserializer for the list of `<entry>` in the response.
 pub struct ResponseDeserializer<'a, 'de> {
     reader: &'a mut ResponseReader<'de>,
+    /// The entry whose identifier was passed to `next_key_seed`.
+    pending: Option<(&'de [u8], RawEntry<'de>)>,
 }
 
 impl<'a, 'de> ResponseDeserializer<'a, 'de> {
     pub fn from_reader(reader: &'a mut ResponseReader<'de>) -> Self {
-        Self { reader }
+        Self {
+            reader,
+            pending: None,
+        }
     }
 }
 
@@ -37,13 +42,15 @@ impl<'a, 'de> Deserializer<'de> for ResponseDeserializer<'a, 'de> {
     where
         V: Visitor<'de>,
     {
-        match self.reader.next_id()? {
-            Some(id) => {
-                let val = visitor.visit_some(EntryDeserializer {
-                    reader: &mut *self.reader,
-                    id: Some(id),
-                });
-                if !self.reader.next_id()?.is_none() {
+        match self.reader.next_entry()? {
+            Some((id, entry)) => {
+                let val = visitor
+                    .visit_some(EntryDeserializer {
+                        id: Some(id),
+                        entry,
+                    })
+                    .map_err(|err| self.reader.entry_error(err, Some(id)));
+                if self.reader.next_entry()?.is_some() {
                     Err(ResponseError::TrailingEntries)
                 } else {
                     val
@@ -67,7 +74,7 @@ impl<'a, 'de> Deserializer<'de> for ResponseDeserializer<'a, 'de> {
     where
         V: Visitor<'de>,
     {
-        while self.reader.next_id()?.is_some() {}
+        while self.reader.next_entry()?.is_some() {}
         visitor.visit_unit()
     }
 
@@ -85,13 +92,14 @@ impl<'a, 'de> SeqAccess<'de> for ResponseDeserializer<'a, 'de> {
     where
         T: serde::de::DeserializeSeed<'de>,
     {
-        match self.reader.next_id()? {
-            Some(id) => seed
+        match self.reader.next_entry()? {
+            Some((id, entry)) => seed
                 .deserialize(EntryDeserializer {
-                    reader: &mut *self.reader,
                     id: Some(id),
+                    entry,
                 })
-                .map(Some),
+                .map(Some)
+                .map_err(|err| self.reader.entry_error(err, Some(id))),
             None => Ok(None),
         }
     }
@@ -104,8 +112,13 @@ impl<'a, 'de> MapAccess<'de> for ResponseDeserializer<'a, 'de> {
     where
         K: serde::de::DeserializeSeed<'de>,
     {
-        match self.reader.next_id()? {
-            Some(id) => seed.deserialize(IdDeserializer { id }).map(Some),
+        match self.reader.next_entry()? {
+            Some((id, entry)) => {
+                self.pending = Some((id, entry));
+                seed.deserialize(IdDeserializer { id })
+                    .map(Some)
+                    .map_err(|err| self.reader.entry_error(err.in_field("id"), Some(id)))
+            }
             None => Ok(None),
         }
     }
@@ -116,10 +129,12 @@ impl<'a, 'de> MapAccess<'de> for ResponseDeserializer<'a, 'de> {
     {
         // we already passed the `id` key to `next_key_seed`, so we deserialize as
         // though it does not exist
-        seed.deserialize(EntryDeserializer {
-            reader: &mut *self.reader,
-            id: None,
-        })
+        let (id, entry) = self
+            .pending
+            .take()
+            .expect("`next_value_seed` called before `next_key_seed`");
+        seed.deserialize(EntryDeserializer { id: None, entry })
+            .map_err(|err| self.reader.entry_error(err, Some(id)))
     }
 }
 
@@ -191,12 +206,12 @@ impl<'de> Deserializer<'de> for IdDeserializer<'de> {
     }
 }
 
-pub struct EntryDeserializer<'a, 'de> {
-    reader: &'a mut ResponseReader<'de>,
+pub struct EntryDeserializer<'de> {
     id: Option<&'de [u8]>,
+    entry: RawEntry<'de>,
 }
 
-static ALLOWED_FIELDS: [&str; 11] = [
+static ALLOWED_FIELDS: [&str; 12] = [
     "id",
     "title",
     "updated",
@@ -208,9 +223,10 @@ static ALLOWED_FIELDS: [&str; 11] = [
     "journal_ref",
     "authors",
     "doi",
+    "links",
 ];
 
-impl<'a, 'de> Deserializer<'de> for EntryDeserializer<'a, 'de> {
+impl<'de> Deserializer<'de> for EntryDeserializer<'de> {
     type Error = ResponseError;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -240,10 +256,10 @@ impl<'a, 'de> Deserializer<'de> for EntryDeserializer<'a, 'de> {
     where
         V: Visitor<'de>,
     {
-        let Self { reader, id } = self;
+        let Self { id, entry } = self;
         visitor.visit_map(EntryMapAccess {
-            reader,
             id,
+            entry,
             fields,
             idx: 0,
         })
@@ -263,21 +279,21 @@ impl<'a, 'de> Deserializer<'de> for EntryDeserializer<'a, 'de> {
     }
 }
 
-pub struct EntryMapAccess<'a, 'de> {
-    reader: &'a mut ResponseReader<'de>,
+pub struct EntryMapAccess<'de> {
     id: Option<&'de [u8]>,
+    entry: RawEntry<'de>,
     fields: &'static [&'static str],
     idx: usize,
 }
 
-impl<'a, 'de> MapAccess<'de> for EntryMapAccess<'a, 'de> {
+impl<'de> MapAccess<'de> for EntryMapAccess<'de> {
     type Error = ResponseError;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
     where
         K: serde::de::DeserializeSeed<'de>,
     {
-        while self.idx < 11 {
+        while self.idx < ALLOWED_FIELDS.len() {
             let name = ALLOWED_FIELDS[self.idx];
             if self.fields.contains(&name) {
                 return seed
@@ -309,64 +325,71 @@ impl<'a, 'de> MapAccess<'de> for EntryMapAccess<'a, 'de> {
             }
             // title
             1 => seed.deserialize(StrTagDeserializer {
-                reader: &mut *self.reader,
-                getter: ResponseReader::next_title,
+                value: require(self.entry.title.take(), "title")?,
             }),
             // updated
             2 => seed.deserialize(StrTagDeserializer {
-                reader: &mut *self.reader,
-                getter: ResponseReader::next_updated,
+                value: require(self.entry.updated.take(), "updated")?,
             }),
             // summary
             3 => seed.deserialize(StrTagDeserializer {
-                reader: &mut *self.reader,
-                getter: ResponseReader::next_summary,
+                value: require(self.entry.summary.take(), "summary")?,
             }),
             // category..
             4 => seed.deserialize(CategorySeqAccess {
-                reader: &mut *self.reader,
+                terms: std::mem::take(&mut self.entry.categories).into_iter(),
+                idx: 0,
             }),
             // published
             5 => seed.deserialize(StrTagDeserializer {
-                reader: &mut *self.reader,
-                getter: ResponseReader::next_published,
+                value: require(self.entry.published.take(), "published")?,
             }),
             // comment?
             6 => seed.deserialize(StrTagOptDeserializer {
-                reader: &mut *self.reader,
-                getter: ResponseReader::next_comment,
+                value: self.entry.comment.take(),
             }),
             // primary_category
-            7 => {
-                let term = self.reader.next_primary_category()?;
-                seed.deserialize(TermDeserializer { term })
-            }
+            7 => seed.deserialize(TermDeserializer {
+                term: require(self.entry.primary_category.take(), "primary_category")?,
+            }),
             // journal_ref?
             8 => seed.deserialize(StrTagOptDeserializer {
-                reader: &mut *self.reader,
-                getter: ResponseReader::next_journal_ref,
+                value: self.entry.journal_ref.take(),
             }),
             // author..
             9 => seed.deserialize(AuthorSeqAccess {
-                reader: &mut *self.reader,
+                authors: std::mem::take(&mut self.entry.authors).into_iter(),
+                idx: 0,
             }),
             // doi?
             10 => seed.deserialize(StrTagOptDeserializer {
-                reader: &mut *self.reader,
-                getter: ResponseReader::next_doi,
+                value: self.entry.doi.take(),
+            }),
+            // links
+            11 => seed.deserialize(LinksDeserializer {
+                links: self.entry.links()?,
+                idx: 0,
             }),
             _ => unreachable!(),
         };
+        let val = val.map_err(|err| err.in_field(ALLOWED_FIELDS[self.idx]));
         self.idx += 1;
         val
     }
 }
 
-pub struct AuthorSeqAccess<'a, 'de> {
-    reader: &'a mut ResponseReader<'de>,
+/// Returns the contents of a required tag, or a [`ResponseError::MissingTag`] error.
+fn require<T>(value: Option<T>, tag: &'static str) -> Result<T, ResponseError> {
+    value.ok_or(ResponseError::MissingTag(tag))
 }
 
-impl<'a, 'de> Deserializer<'de> for AuthorSeqAccess<'a, 'de> {
+pub struct AuthorSeqAccess<'de> {
+    authors: vec::IntoIter<RawAuthor<'de>>,
+    /// The index of the next author.
+    idx: usize,
+}
+
+impl<'de> Deserializer<'de> for AuthorSeqAccess<'de> {
     type Error = ResponseError;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -401,30 +424,31 @@ impl<'a, 'de> Deserializer<'de> for AuthorSeqAccess<'a, 'de> {
     }
 }
 
-impl<'a, 'de> SeqAccess<'de> for AuthorSeqAccess<'a, 'de> {
+impl<'de> SeqAccess<'de> for AuthorSeqAccess<'de> {
     type Error = ResponseError;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
     where
         T: serde::de::DeserializeSeed<'de>,
     {
-        if self.reader.next_author()? {
-            seed.deserialize(AuthorDeserializer {
-                reader: &mut *self.reader,
-                idx: 0,
-            })
-            .map(Some)
-        } else {
-            Ok(None)
-        }
+        let Some(author) = self.authors.next() else {
+            return Ok(None);
+        };
+        let val = seed
+            .deserialize(AuthorDeserializer { author, idx: 0 })
+            .map_err(|err| err.in_field(format_args!("[{}]", self.idx)));
+        self.idx += 1;
+        val.map(Some)
     }
 }
 
-pub struct CategorySeqAccess<'a, 'de> {
-    reader: &'a mut ResponseReader<'de>,
+pub struct CategorySeqAccess<'de> {
+    terms: vec::IntoIter<Term<'de>>,
+    /// The index of the next category.
+    idx: usize,
 }
 
-impl<'a, 'de> Deserializer<'de> for CategorySeqAccess<'a, 'de> {
+impl<'de> Deserializer<'de> for CategorySeqAccess<'de> {
     type Error = ResponseError;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -459,17 +483,21 @@ impl<'a, 'de> Deserializer<'de> for CategorySeqAccess<'a, 'de> {
     }
 }
 
-impl<'a, 'de> SeqAccess<'de> for CategorySeqAccess<'a, 'de> {
+impl<'de> SeqAccess<'de> for CategorySeqAccess<'de> {
     type Error = ResponseError;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
     where
         T: serde::de::DeserializeSeed<'de>,
     {
-        match self.reader.next_category()? {
-            Some(term) => seed.deserialize(TermDeserializer { term }).map(Some),
-            None => Ok(None),
-        }
+        let Some(term) = self.terms.next() else {
+            return Ok(None);
+        };
+        let val = seed
+            .deserialize(TermDeserializer { term })
+            .map_err(|err| err.in_field(format_args!("[{}]", self.idx)));
+        self.idx += 1;
+        val.map(Some)
     }
 }
 
@@ -524,12 +552,22 @@ impl<'de> Deserializer<'de> for TermDeserializer<'de> {
     }
 }
 
-pub struct AuthorDeserializer<'a, 'de> {
-    reader: &'a mut ResponseReader<'de>,
+/// The keys of the map produced by an [`AuthorDeserializer`].
+static AUTHOR_FIELDS: [&str; 2] = ["name", "affiliation"];
+
+pub struct AuthorDeserializer<'de> {
+    author: RawAuthor<'de>,
     idx: usize,
 }
 
-impl<'a, 'de> Deserializer<'de> for AuthorDeserializer<'a, 'de> {
+impl<'de> AuthorDeserializer<'de> {
+    /// Take the name of the author.
+    fn name(&mut self) -> Result<Cow<'de, str>, ResponseError> {
+        require(self.author.name.take(), "name")
+    }
+}
+
+impl<'de> Deserializer<'de> for AuthorDeserializer<'de> {
     type Error = ResponseError;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -567,26 +605,26 @@ impl<'a, 'de> Deserializer<'de> for AuthorDeserializer<'a, 'de> {
         self.deserialize_seq(visitor)
     }
 
-    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_str<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.reader.next_author_name()? {
-            Cow::Borrowed(name) => visitor.visit_borrowed_str(&name),
+        match self.name()? {
+            Cow::Borrowed(name) => visitor.visit_borrowed_str(name),
             Cow::Owned(name) => visitor.visit_string(name),
         }
     }
 
-    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_string<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        let name = self.reader.next_author_name()?;
+        let name = self.name()?;
         visitor.visit_string(String::from(name))
     }
 
     fn deserialize_enum<V>(
-        self,
+        mut self,
         _name: &'static str,
         _variants: &'static [&'static str],
         visitor: V,
@@ -594,7 +632,7 @@ impl<'a, 'de> Deserializer<'de> for AuthorDeserializer<'a, 'de> {
     where
         V: Visitor<'de>,
     {
-        match self.reader.next_author_name()? {
+        match self.name()? {
             Cow::Borrowed(s) => visitor.visit_enum(BorrowedStrDeserializer::new(s)),
             Cow::Owned(s) => visitor.visit_enum(s.into_deserializer()),
         }
@@ -607,7 +645,7 @@ impl<'a, 'de> Deserializer<'de> for AuthorDeserializer<'a, 'de> {
     }
 }
 
-impl<'a, 'de> SeqAccess<'de> for AuthorDeserializer<'a, 'de> {
+impl<'de> SeqAccess<'de> for AuthorDeserializer<'de> {
     type Error = ResponseError;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
@@ -616,35 +654,31 @@ impl<'a, 'de> SeqAccess<'de> for AuthorDeserializer<'a, 'de> {
     {
         let val = match self.idx {
             0 => seed.deserialize(StrTagDeserializer {
-                reader: &mut *self.reader,
-                getter: ResponseReader::next_author_name,
+                value: self.name()?,
             }),
             1 => seed.deserialize(StrTagOptDeserializer {
-                reader: &mut *self.reader,
-                getter: ResponseReader::next_author_affiliation,
+                value: self.author.affiliation.take(),
             }),
             _ => return Ok(None),
         };
+        let val = val.map_err(|err| err.in_field(AUTHOR_FIELDS[self.idx]));
         self.idx += 1;
         val.map(Some)
     }
 }
 
-impl<'a, 'de> MapAccess<'de> for AuthorDeserializer<'a, 'de> {
+impl<'de> MapAccess<'de> for AuthorDeserializer<'de> {
     type Error = ResponseError;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
     where
         K: serde::de::DeserializeSeed<'de>,
     {
-        match self.idx {
-            0 => seed
-                .deserialize(BorrowedStrDeserializer::new("name"))
+        match AUTHOR_FIELDS.get(self.idx) {
+            Some(key) => seed
+                .deserialize(BorrowedStrDeserializer::new(key))
                 .map(Some),
-            1 => seed
-                .deserialize(BorrowedStrDeserializer::new("affiliation"))
-                .map(Some),
-            _ => Ok(None),
+            None => Ok(None),
         }
     }
 
@@ -654,34 +688,33 @@ impl<'a, 'de> MapAccess<'de> for AuthorDeserializer<'a, 'de> {
     {
         let val = match self.idx {
             0 => seed.deserialize(StrTagDeserializer {
-                reader: &mut *self.reader,
-                getter: ResponseReader::next_author_name,
+                value: self.name()?,
             }),
             1 => seed.deserialize(StrTagOptDeserializer {
-                reader: &mut *self.reader,
-                getter: ResponseReader::next_author_affiliation,
+                value: self.author.affiliation.take(),
             }),
             _ => unreachable!(),
         };
+        let val = val.map_err(|err| err.in_field(AUTHOR_FIELDS[self.idx]));
         self.idx += 1;
         val
     }
 }
 
-pub struct StrTagDeserializer<'a, 'de> {
-    reader: &'a mut ResponseReader<'de>,
-    getter: fn(&'a mut ResponseReader<'de>) -> Result<Cow<'de, str>, ResponseError>,
+/// A deserializer for the text contents of a tag.
+pub struct StrTagDeserializer<'de> {
+    value: Cow<'de, str>,
 }
 
-impl<'a, 'de> Deserializer<'de> for StrTagDeserializer<'a, 'de> {
+impl<'de> Deserializer<'de> for StrTagDeserializer<'de> {
     type Error = ResponseError;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match (self.getter)(&mut *self.reader)? {
-            Cow::Borrowed(name) => visitor.visit_borrowed_str(&name),
+        match self.value {
+            Cow::Borrowed(name) => visitor.visit_borrowed_str(name),
             Cow::Owned(name) => visitor.visit_string(name),
         }
     }
@@ -690,8 +723,7 @@ impl<'a, 'de> Deserializer<'de> for StrTagDeserializer<'a, 'de> {
     where
         V: Visitor<'de>,
     {
-        let v = (self.getter)(&mut *self.reader)?;
-        visitor.visit_string(String::from(v))
+        visitor.visit_string(String::from(self.value))
     }
 
     fn deserialize_newtype_struct<V>(
@@ -721,7 +753,7 @@ impl<'a, 'de> Deserializer<'de> for StrTagDeserializer<'a, 'de> {
     where
         V: Visitor<'de>,
     {
-        match (self.getter)(&mut *self.reader)? {
+        match self.value {
             Cow::Borrowed(s) => visitor.visit_enum(BorrowedStrDeserializer::new(s)),
             Cow::Owned(s) => visitor.visit_enum(s.into_deserializer()),
         }
@@ -734,19 +766,19 @@ impl<'a, 'de> Deserializer<'de> for StrTagDeserializer<'a, 'de> {
     }
 }
 
-pub struct StrTagOptDeserializer<'a, 'de> {
-    reader: &'a mut ResponseReader<'de>,
-    getter: fn(&'a mut ResponseReader<'de>) -> Result<Option<Cow<'de, str>>, ResponseError>,
+/// A deserializer for the text contents of an optional tag.
+pub struct StrTagOptDeserializer<'de> {
+    value: Option<Cow<'de, str>>,
 }
 
-impl<'a, 'de> Deserializer<'de> for StrTagOptDeserializer<'a, 'de> {
+impl<'de> Deserializer<'de> for StrTagOptDeserializer<'de> {
     type Error = ResponseError;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match (self.getter)(&mut *self.reader)? {
+        match self.value {
             Some(Cow::Borrowed(s)) => visitor.visit_some(BorrowedStrDeserializer::new(s)),
             Some(Cow::Owned(s)) => visitor.visit_some(s.into_deserializer()),
             None => visitor.visit_none(),
@@ -757,7 +789,7 @@ impl<'a, 'de> Deserializer<'de> for StrTagOptDeserializer<'a, 'de> {
     where
         V: Visitor<'de>,
     {
-        match (self.getter)(&mut *self.reader)? {
+        match self.value {
             Some(v) => visitor.visit_string(String::from(v)),
             None => visitor.visit_none(),
         }
@@ -767,8 +799,8 @@ impl<'a, 'de> Deserializer<'de> for StrTagOptDeserializer<'a, 'de> {
     where
         V: Visitor<'de>,
     {
-        match (self.getter)(&mut *self.reader)? {
-            Some(Cow::Borrowed(v)) => visitor.visit_borrowed_str(&v),
+        match self.value {
+            Some(Cow::Borrowed(v)) => visitor.visit_borrowed_str(v),
             Some(Cow::Owned(v)) => visitor.visit_string(v),
             None => visitor.visit_none(),
         }
@@ -794,7 +826,7 @@ impl<'a, 'de> Deserializer<'de> for StrTagOptDeserializer<'a, 'de> {
     where
         V: Visitor<'de>,
     {
-        match (self.getter)(&mut *self.reader)? {
+        match self.value {
             Some(Cow::Borrowed(s)) => visitor.visit_enum(BorrowedStrDeserializer::new(s)),
             Some(Cow::Owned(s)) => visitor.visit_enum(s.into_deserializer()),
             None => visitor.visit_none(),
@@ -814,3 +846,79 @@ impl<'a, 'de> Deserializer<'de> for StrTagOptDeserializer<'a, 'de> {
         tuple_struct map struct option
     }
 }
+
+/// The keys of the map produced by a [`LinksDeserializer`].
+static LINK_KEYS: [&str; 3] = ["abs", "pdf", "doi"];
+
+/// A deserializer for the links in an entry, as a map with optional `abs`, `pdf` and `doi` keys.
+pub struct LinksDeserializer<'de> {
+    links: Links<'de>,
+    idx: usize,
+}
+
+impl<'de> Deserializer<'de> for LinksDeserializer<'de> {
+    type Error = ResponseError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct enum identifier
+    }
+}
+
+impl<'de> MapAccess<'de> for LinksDeserializer<'de> {
+    type Error = ResponseError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: serde::de::DeserializeSeed<'de>,
+    {
+        match LINK_KEYS.get(self.idx) {
+            Some(key) => seed
+                .deserialize(BorrowedStrDeserializer::new(key))
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::DeserializeSeed<'de>,
+    {
+        let link = match self.idx {
+            0 => self.links.abs.take(),
+            1 => self.links.pdf.take(),
+            2 => self.links.doi.take(),
+            _ => unreachable!(),
+        };
+        let key = LINK_KEYS[self.idx];
+        self.idx += 1;
+        seed.deserialize(StrTagOptDeserializer { value: link })
+            .map_err(|err| err.in_field(key))
+    }
+}
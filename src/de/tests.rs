@@ -69,6 +69,8 @@ fn test_query_de() {
         /// The arXiv or ACM or MSC category for an article.
         #[serde(borrow)]
         pub categories: Vec<Cow<'r, str>>,
+        /// The links in the entry.
+        pub links: crate::response::Links<'static>,
         extra: Option<String>,
     }
 
@@ -119,6 +121,15 @@ fn test_query_de() {
         "10.1103/PhysRevA.88.062514"
     );
 
+    assert_eq!(
+        response.entries[9].links.doi.as_deref(),
+        Some("https://doi.org/10.1103/PhysRevA.88.062514")
+    );
+    assert_eq!(
+        response.entries[9].links.pdf.as_deref(),
+        Some("https://arxiv.org/pdf/1401.3666v2")
+    );
+
     assert_eq!(response.entries[9].categories[2], "nucl-th");
     assert_eq!(
         response.entries[9].published,
@@ -133,3 +144,44 @@ fn test_query_de() {
     );
     assert!(response.entries.is_empty());
 }
+
+#[test]
+fn test_error_context() {
+    use serde::Deserialize;
+
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Author {
+        name: u32,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Entry {
+        authors: Vec<Author>,
+    }
+
+    let xml = include_str!("../response/tests/query.xml").as_bytes();
+    let err = Response::<Vec<Entry>>::from_xml(xml).unwrap_err();
+    assert!(matches!(err.without_context(), ResponseError::Custom(_)));
+    let context = err.context().unwrap();
+    assert_eq!(context.index, Some(0));
+    assert_eq!(
+        context.id,
+        Some(ArticleId::parse("nucl-ex/0408020v1").unwrap())
+    );
+    assert_eq!(context.field.as_deref(), Some("authors[0].name"));
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Categories {
+        categories: Vec<u32>,
+    }
+
+    let err = Response::<BTreeMap<ArticleId, Categories>>::from_xml(xml).unwrap_err();
+    let context = err.context().unwrap();
+    assert_eq!(context.field.as_deref(), Some("categories[0]"));
+    assert!(xml[context.position.unwrap()..].starts_with(b"<entry>"));
+}
@@ -108,12 +108,12 @@ fn test_date_new() {
             assert!(date_new([b'0', b'0', b, b'2']).is_err());
         }
 
-        if !(b'0'..=b'9').contains(&b) {
+        if !b.is_ascii_digit() {
             println!("{b}");
             assert!(date_new([b'0', b, b'0', b'2']).is_err());
         }
 
-        if !(b'0'..=b'9').contains(&b) {
+        if !b.is_ascii_digit() {
             println!("{b}");
             assert!(date_new([b, b'0', b'0', b'2']).is_err());
         }
@@ -5,12 +5,17 @@
 //! 2. The [`query`] module provides a builder interface to generate URLs to make requests to the
 //!    arXiv API service.
 //! 3. The [`response`] module a function to parse the XML response obtained
-//!    from the arXiv API service.
+//!    from the arXiv API service, and a function to write responses in the same format.
 //! 4. The [`de`] module provides methods to deserialize the API response into your own types using
 //!    a flexible [`serde`] interface.
+//! 5. The [`client`] module provides a blocking client which sends queries through a pluggable
+//!    transport. This requires the `client` feature.
+//! 6. The [`mock`] module provides a local imitation of the arXiv API for integration tests. This
+//!    requires the `mock-server` feature.
 //!
-//! Notably, this crate will not make the network request itself. For that, you might use a crate
-//! such as [reqwest](https://crates.io/crates/reqwest) or [ureq](https://crates.io/crates/ureq).
+//! By default, this crate will not make the network request itself. Either enable the `ureq`
+//! feature to use the built-in [ureq](https://crates.io/crates/ureq) transport, or implement
+//! [`client::Transport`] for a crate such as [reqwest](https://crates.io/crates/reqwest).
 //!
 //! ## Examples
 //! See the [examples](https://github.com/autobib/rsxiv/blob/master/examples/README.md) directory
@@ -22,10 +27,16 @@
 #![deny(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+#[cfg(feature = "client")]
+#[cfg_attr(docsrs, doc(cfg(feature = "client")))]
+pub mod client;
 #[cfg(feature = "serde")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 pub mod de;
 pub mod id;
+#[cfg(feature = "mock-server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mock-server")))]
+pub mod mock;
 pub mod query;
 pub mod response;
 mod xml;
@@ -0,0 +1,436 @@
+//! # Mock arXiv API server
+//!
+//! This module provides a local imitation of the arXiv API `/api/query` endpoint, for integration
+//! tests which should not depend on the network. A [`MockApi`] holds a corpus of entries and
+//! evaluates queries against it, and a [`MockServer`] serves a [`MockApi`] over HTTP on a local
+//! port.
+//!
+//! The mock evaluates the `search_query`, `id_list`, `start`, `max_results`, `sortBy` and
+//! `sortOrder` parameters, and returns Atom feeds in the format of the arXiv API. Invalid
+//! parameters result in an arXiv-style error feed with status `400`, which is reported as a
+//! [`ResponseError::Arxiv`] when parsed.
+//!
+//! The search is an approximation of the arXiv search engine. Only search queries accepted by
+//! [`Expr::parse`] are supported, and entries are matched using [`Expr::matches`]. Results sorted
+//! by relevance are returned in the order of the corpus.
+//!
+//! This module requires the `mock-server` feature.
+//!
+//! ### Example
+//! ```
+//! use rsxiv::{
+//!     id::ArticleId,
+//!     mock::{MockApi, MockServer},
+//!     query::{Field, Query},
+//!     response::parse,
+//! };
+//!
+//! // abridged arXiv response
+//! let xml = // r#"<feed xmlns...
+#![doc = include_str!("response/tests/query_doc.txt")]
+//! let server = MockServer::start(MockApi::from_xml(xml.as_bytes()).unwrap()).unwrap();
+//!
+//! let mut query = Query::new();
+//! query
+//!     .base_url(server.base_url())
+//!     .unwrap()
+//!     .search_query()
+//!     .init(Field::ti("assouad spectra").unwrap());
+//!
+//! let body = ureq::get(query.url().as_str())
+//!     .call()
+//!     .unwrap()
+//!     .into_body()
+//!     .read_to_vec()
+//!     .unwrap();
+//! let response = parse(&body).unwrap();
+//! assert_eq!(response.entries[0].id, ArticleId::parse("2206.06921v3").unwrap());
+//! ```
+#[cfg(test)]
+mod tests;
+
+use std::{
+    cmp::Reverse,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use chrono::{DateTime, FixedOffset, SubsecRound, Utc};
+use url::{Url, form_urlencoded};
+
+use crate::{
+    id::ArticleId,
+    query::{Expr, MAX_RESULTS, SortBy, SortOrder},
+    response::{OwnedEntry, Pagination, ResponseError, parse, write_error, write_feed},
+};
+
+/// The default number of results per page, if `max_results` is not set.
+const DEFAULT_MAX_RESULTS: u64 = 10;
+
+/// The largest request body accepted by the [`MockServer`], in bytes.
+const MAX_BODY_LEN: usize = 1024 * 1024;
+
+/// A response of the [`MockApi`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockResponse {
+    /// The HTTP status code, which is `200` for a valid query and `400` for an invalid query.
+    pub status: u16,
+    /// The Atom feed.
+    pub body: String,
+}
+
+/// A corpus of entries which answers arXiv API queries.
+///
+/// See the [module documentation](self) for the supported query syntax.
+#[derive(Debug, Clone)]
+pub struct MockApi {
+    entries: Vec<OwnedEntry>,
+    updated: DateTime<FixedOffset>,
+}
+
+impl Default for MockApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockApi {
+    /// Construct a new mock with no entries.
+    ///
+    /// The `updated` timestamp of each feed is the time the mock was constructed.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            updated: Utc::now().trunc_subsecs(0).fixed_offset(),
+        }
+    }
+
+    /// Construct a new mock containing the entries in an arXiv API response.
+    ///
+    /// The `updated` timestamp of each feed is the timestamp of the response.
+    pub fn from_xml(xml: &[u8]) -> Result<Self, ResponseError> {
+        let response = parse(xml)?.into_owned();
+        Ok(Self {
+            entries: response.entries,
+            updated: response.updated,
+        })
+    }
+
+    /// Add entries to the corpus.
+    pub fn extend<I: IntoIterator<Item = OwnedEntry>>(&mut self, entries: I) -> &mut Self {
+        self.entries.extend(entries);
+        self
+    }
+
+    /// Set the `updated` timestamp of each feed.
+    pub fn updated(&mut self, updated: DateTime<FixedOffset>) -> &mut Self {
+        self.updated = updated;
+        self
+    }
+
+    /// Returns the entries in the corpus.
+    #[must_use]
+    pub fn entries(&self) -> &[OwnedEntry] {
+        &self.entries
+    }
+
+    /// Respond to a query, provided as the URL-encoded query string of a `GET` request or the body
+    /// of a `POST` request.
+    ///
+    /// ### Example
+    /// ```
+    /// use rsxiv::{
+    ///     mock::MockApi,
+    ///     response::{ResponseError, parse},
+    /// };
+    ///
+    /// let api = MockApi::new();
+    /// let response = api.respond("id_list=2301.00001&start=0&max_results=10");
+    /// assert_eq!(response.status, 200);
+    /// assert!(parse(response.body.as_bytes()).unwrap().entries.is_empty());
+    ///
+    /// let response = api.respond("id_list=2301.0001");
+    /// assert_eq!(response.status, 400);
+    /// assert!(matches!(
+    ///     parse(response.body.as_bytes()),
+    ///     Err(ResponseError::Arxiv(msg)) if msg == "incorrect id format for 2301.0001"
+    /// ));
+    /// ```
+    #[must_use]
+    pub fn respond(&self, params: &str) -> MockResponse {
+        let mut body = Vec::new();
+        let status = match self.evaluate(params) {
+            Ok((pagination, entries)) => {
+                let title = format!("arXiv Query: {params}");
+                write_feed(&mut body, &title, &self.updated, &pagination, entries)
+                    .expect("writing to a `Vec` cannot fail");
+                200
+            }
+            Err(message) => {
+                write_error(&mut body, &self.updated, &message)
+                    .expect("writing to a `Vec` cannot fail");
+                400
+            }
+        };
+        MockResponse {
+            status,
+            body: String::from_utf8(body).expect("the feed is written from strings"),
+        }
+    }
+
+    /// Evaluate the query, returning the pagination and the entries on the requested page, or an
+    /// error message.
+    fn evaluate(&self, params: &str) -> Result<(Pagination, Vec<&OwnedEntry>), String> {
+        let mut search_query = None;
+        let mut id_list = None;
+        let mut start = 0;
+        let mut max_results = DEFAULT_MAX_RESULTS;
+        let mut sort_by = SortBy::Relevance;
+        let mut sort_order = SortOrder::Descending;
+
+        for (key, value) in form_urlencoded::parse(params.as_bytes()) {
+            match key.as_ref() {
+                "search_query" => search_query = Some(value.into_owned()),
+                "id_list" => id_list = Some(value.into_owned()),
+                "start" => start = parse_count("start", &value)?,
+                "max_results" => {
+                    max_results = parse_count("max_results", &value)?;
+                    if max_results > u64::from(MAX_RESULTS) {
+                        return Err(format!("max_results must be <= {MAX_RESULTS}"));
+                    }
+                }
+                "sortBy" => {
+                    sort_by = match value.as_ref() {
+                        "relevance" => SortBy::Relevance,
+                        "lastUpdatedDate" => SortBy::LastUpdatedDate,
+                        "submittedDate" => SortBy::SubmittedDate,
+                        _ => {
+                            return Err(
+                                "sortBy must be in: relevance, lastUpdatedDate, submittedDate"
+                                    .to_owned(),
+                            );
+                        }
+                    }
+                }
+                "sortOrder" => {
+                    sort_order = match value.as_ref() {
+                        "ascending" => SortOrder::Ascending,
+                        "descending" => SortOrder::Descending,
+                        _ => return Err("sortOrder must be in: ascending, descending".to_owned()),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let expr = match search_query.as_deref() {
+            None | Some("") => None,
+            Some(search_query) => Some(
+                Expr::parse(search_query)
+                    .ok_or_else(|| format!("malformed search query {search_query}"))?,
+            ),
+        };
+        let ids = match id_list.as_deref() {
+            None | Some("") => None,
+            Some(id_list) => Some(
+                id_list
+                    .split(',')
+                    .map(|id| {
+                        ArticleId::parse(id).map_err(|_| format!("incorrect id format for {id}"))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        };
+
+        let mut results: Vec<&OwnedEntry> = match &ids {
+            Some(ids) => ids
+                .iter()
+                .filter_map(|id| self.find(*id))
+                .filter(|entry| expr.as_ref().is_none_or(|expr| expr.matches(entry)))
+                .collect(),
+            None => match &expr {
+                Some(expr) => self.entries.iter().filter(|e| expr.matches(e)).collect(),
+                None => Vec::new(),
+            },
+        };
+
+        let key: fn(&&OwnedEntry) -> DateTime<FixedOffset> = match sort_by {
+            SortBy::Relevance => |_| DateTime::UNIX_EPOCH.fixed_offset(),
+            SortBy::LastUpdatedDate => |entry| entry.updated,
+            SortBy::SubmittedDate => |entry| entry.published,
+        };
+        // the sort is stable, so results sorted by relevance remain in the order of the corpus
+        match sort_order {
+            SortOrder::Ascending => results.sort_by_key(key),
+            SortOrder::Descending => results.sort_by_key(|entry| Reverse(key(entry))),
+        }
+
+        let pagination = Pagination {
+            total_results: results.len() as u64,
+            start_index: start,
+            items_per_page: max_results,
+        };
+        let page = results
+            .into_iter()
+            .skip(usize::try_from(start).unwrap_or(usize::MAX))
+            .take(usize::try_from(max_results).unwrap_or(usize::MAX))
+            .collect();
+        Ok((pagination, page))
+    }
+
+    /// Find the entry with the identifier, ignoring the version.
+    fn find(&self, id: ArticleId) -> Option<&OwnedEntry> {
+        let id = id.clear_version();
+        self.entries
+            .iter()
+            .find(|entry| entry.id.clear_version() == id)
+    }
+}
+
+/// Parse a non-negative integer parameter, returning an arXiv-style error message otherwise.
+fn parse_count(name: &str, value: &str) -> Result<u64, String> {
+    match value.parse::<i64>() {
+        Ok(count) => u64::try_from(count).map_err(|_| format!("{name} must be non-negative")),
+        Err(_) => Err(format!("{name} must be an integer")),
+    }
+}
+
+/// A local HTTP server which serves a [`MockApi`].
+///
+/// The server listens on a random port of the loopback interface, and answers `GET` and `POST`
+/// requests to `/api/query`. Set the [base URL](crate::query::Query::base_url) of a query to
+/// [`MockServer::base_url`] to send it to the server. The server stops when it is dropped.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    api: Arc<MockApi>,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Start a server for the mock in a background thread.
+    pub fn start(api: MockApi) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        let addr = listener.local_addr()?;
+        let api = Arc::new(api);
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let handle = std::thread::spawn({
+            let api = api.clone();
+            let shutdown = shutdown.clone();
+            move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::Acquire) {
+                        break;
+                    }
+                    // a failed connection only affects the client which made it
+                    if let Ok(stream) = stream {
+                        let _ = handle_connection(&api, stream);
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            addr,
+            api,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// The address on which the server is listening.
+    #[must_use]
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The base URL of the server, for use with [`Query::base_url`](crate::query::Query::base_url).
+    #[must_use]
+    pub fn base_url(&self) -> Url {
+        Url::parse(&format!("http://{}/", self.addr)).expect("socket address is a valid host")
+    }
+
+    /// Returns a reference to the mock served by the server.
+    #[must_use]
+    pub fn api(&self) -> &MockApi {
+        &self.api
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Release);
+        // wake the listener so that it observes the shutdown
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Read a single HTTP request from the stream and write the response.
+fn handle_connection(api: &MockApi, stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(&stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+
+    let (path, query_string) = target.split_once('?').unwrap_or((target, ""));
+    let (status, body) = match (method, path) {
+        ("GET", "/api/query") => {
+            let response = api.respond(query_string);
+            (response.status, response.body)
+        }
+        ("POST", "/api/query") if content_length > MAX_BODY_LEN => (413, String::new()),
+        ("POST", "/api/query") => {
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body)?;
+            let response = api.respond(&String::from_utf8_lossy(&body));
+            (response.status, response.body)
+        }
+        (_, "/api/query") => (405, String::new()),
+        _ => (404, String::new()),
+    };
+
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        413 => "Content Too Large",
+        _ => "Method Not Allowed",
+    };
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: application/atom+xml; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
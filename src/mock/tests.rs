@@ -0,0 +1,192 @@
+use super::*;
+use crate::{
+    Query,
+    query::{Combine, Field},
+};
+
+const QUERY_XML: &[u8] = include_bytes!("../response/tests/query.xml");
+
+/// The identifiers of the entries in the response to the query.
+fn ids(api: &MockApi, query: &Query) -> Vec<String> {
+    let response = api.respond(query.url().query().unwrap_or(""));
+    assert_eq!(response.status, 200);
+    parse(response.body.as_bytes())
+        .unwrap()
+        .entries
+        .iter()
+        .map(|entry| entry.id.to_string())
+        .collect()
+}
+
+fn search(field: Field<&str>) -> Query {
+    let mut query = Query::new();
+    query.search_query().init(field);
+    query
+}
+
+#[test]
+fn test_mock_api() {
+    let api = MockApi::from_xml(QUERY_XML).unwrap();
+    assert_eq!(api.entries().len(), 10);
+
+    // the written feed is parsed back into the same entries
+    let mut query = Query::new();
+    for entry in api.entries() {
+        query.id_list().push(&entry.id);
+    }
+    let response = api.respond(query.url().query().unwrap());
+    let parsed = parse(response.body.as_bytes()).unwrap();
+    assert_eq!(parsed.entries, api.entries());
+    assert_eq!(parsed.updated, parse(QUERY_XML).unwrap().updated);
+    assert_eq!(parsed.pagination.total_results, 10);
+
+    // field searches
+    assert_eq!(
+        ids(&api, &search(Field::au("meissner").unwrap())),
+        ["1205.6628v2"]
+    );
+    assert_eq!(
+        ids(&api, &search(Field::au("de_vries_h").unwrap())),
+        ["nucl-ex/0408020v1"]
+    );
+    assert_eq!(
+        ids(&api, &search(Field::ti("\"two-photon exchange\"").unwrap())),
+        ["nucl-ex/0408020v1"]
+    );
+    assert!(ids(&api, &search(Field::ti("\"exchange two-photon\"").unwrap())).is_empty());
+    assert!(ids(&api, &search(Field::rn("CERN-2013-002").unwrap())).is_empty());
+    assert_eq!(
+        ids(&api, &search(Field::jr("yellow report").unwrap())),
+        ["1309.4668v1"]
+    );
+
+    // boolean operators and date ranges
+    let start = chrono::NaiveDate::from_ymd_opt(2010, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let end = chrono::NaiveDate::from_ymd_opt(2030, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let mut query = Query::new();
+    query
+        .search_query()
+        .init(Field::cat("hep-ph").unwrap())
+        .and(start..end)
+        .and_not(Field::au("rojo").unwrap());
+    assert_eq!(
+        ids(&api, &query),
+        ["1606.02159v1", "1205.6628v2", "1401.3666v2"]
+    );
+
+    // sorting and pagination
+    let mut query = search(Field::all("proton").unwrap());
+    let all = ids(&api, &query);
+    assert_eq!(all.len(), 10);
+    query.sort(SortBy::SubmittedDate, SortOrder::Ascending);
+    assert_eq!(
+        ids(&api, &query)[..2],
+        ["nucl-th/9910021v3", "nucl-ex/0408020v1"]
+    );
+    query.paginate(8, 5).unwrap();
+    assert_eq!(ids(&api, &query), ["1610.08734v3", "2102.00018v2"]);
+    let response = api.respond(query.url().query().unwrap());
+    let pagination = parse(response.body.as_bytes()).unwrap().pagination;
+    assert_eq!(
+        pagination,
+        Pagination {
+            total_results: 10,
+            start_index: 8,
+            items_per_page: 5
+        }
+    );
+
+    // identifiers are returned in order, ignoring versions
+    let mut query = Query::new();
+    query
+        .id_list()
+        .push(&ArticleId::parse("1602.03411").unwrap())
+        .push(&ArticleId::parse("2301.00001").unwrap())
+        .push(&ArticleId::parse("hep-th/9901001").unwrap())
+        .push(&ArticleId::parse("1401.3666v1").unwrap());
+    assert_eq!(ids(&api, &query), ["1602.03411v1", "1401.3666v2"]);
+
+    // errors
+    for (params, message) in [
+        ("id_list=1234.1234v", "incorrect id format for 1234.1234v"),
+        ("search_query=ti:a&start=-1", "start must be non-negative"),
+        (
+            "search_query=ti:a&max_results=a",
+            "max_results must be an integer",
+        ),
+        // the parser does not unescape text
+        (
+            "search_query=ti:a&max_results=2001",
+            "max_results must be &lt;= 2000",
+        ),
+        (
+            "search_query=ti:a&sortBy=date",
+            "sortBy must be in: relevance, lastUpdatedDate, submittedDate",
+        ),
+        ("search_query=(ti:a", "malformed search query (ti:a"),
+    ] {
+        let response = api.respond(params);
+        assert_eq!(response.status, 400);
+        assert!(
+            matches!(parse(response.body.as_bytes()), Err(ResponseError::Arxiv(msg)) if msg == message),
+            "{params}"
+        );
+    }
+}
+
+#[test]
+fn test_mock_server() {
+    let server = MockServer::start(MockApi::from_xml(QUERY_XML).unwrap()).unwrap();
+    let agent = ureq::Agent::new_with_defaults();
+
+    let mut query = search(Field::au("meissner").unwrap());
+    query.base_url(server.base_url()).unwrap();
+
+    let body = agent
+        .get(query.url().as_str())
+        .call()
+        .unwrap()
+        .into_body()
+        .read_to_vec()
+        .unwrap();
+    assert_eq!(parse(&body).unwrap().entries.len(), 1);
+
+    let body = agent
+        .post(query.endpoint().as_str())
+        .content_type("application/x-www-form-urlencoded")
+        .send(query.form_body().as_str())
+        .unwrap()
+        .into_body()
+        .read_to_vec()
+        .unwrap();
+    assert_eq!(parse(&body).unwrap().entries.len(), 1);
+
+    let error = |url: &str| match agent.get(url).call() {
+        Err(ureq::Error::StatusCode(status)) => status,
+        _ => panic!("expected an error status for {url}"),
+    };
+    let base = server.base_url();
+    assert_eq!(error(base.join("api/query?start=x").unwrap().as_str()), 400);
+    assert_eq!(error(base.join("api/other").unwrap().as_str()), 404);
+
+    // the declared length of a request body is checked before it is read
+    let mut stream = TcpStream::connect(server.addr()).unwrap();
+    write!(
+        stream,
+        "POST /api/query HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+        usize::MAX
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 413 "), "{response}");
+
+    drop(server);
+    assert!(agent.get(query.url().as_str()).call().is_err());
+}
@@ -4,20 +4,60 @@
 //! main entry point is the [`Query`] struct.
 //!
 //! [api]: https://info.arxiv.org/help/api/user-manual.html
+mod author;
+mod batch;
+mod category;
+mod eval;
+mod expr;
 mod field;
+mod lint;
+mod plan;
+mod preset;
 mod search;
+#[cfg(feature = "serde")]
+mod serialize;
 #[cfg(test)]
 mod tests;
 
-use std::fmt::Write as _;
+use std::{fmt::Write as _, ops::Range};
 
-use url::Url;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use url::{Url, form_urlencoded};
 
 pub use self::{
-    field::{BooleanOp, Combine, Field, FieldGroup, FieldType},
+    author::AuthorMatch,
+    batch::QueryBatcher,
+    category::Category,
+    expr::{Expr, Term},
+    field::{BooleanOp, Combine, Field, FieldGroup, FieldType, LastUpdated},
+    lint::{Lint, Severity},
+    plan::PagePlanner,
     search::{NonEmptySearchQuery, SearchQuery},
 };
-use crate::id::Identifier;
+use crate::id::{ArticleId, Identifier};
+
+/// The largest value of `start` accepted by the arXiv API.
+pub(crate) const MAX_START: u16 = 30000;
+
+/// The largest value of `max_results` accepted by the arXiv API.
+pub(crate) const MAX_RESULTS: u16 = 2000;
+
+/// The range of possible dates: the first arXiv identifier is from August 1991, and the
+/// identifier format is valid until March 2107.
+fn date_bounds() -> Range<NaiveDateTime> {
+    NaiveDateTime::new(NaiveDate::from_ymd_opt(1991, 8, 1).unwrap(), NaiveTime::MIN)
+        ..NaiveDateTime::new(NaiveDate::from_ymd_opt(2107, 4, 1).unwrap(), NaiveTime::MIN)
+}
+
+/// The 64-bit FNV-1a hash of the bytes.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
 
 /// The ordering by which to sort the query results.
 ///
@@ -64,22 +104,9 @@ impl IdList<'_> {
 
     /// Add identifiers to the list from an iterator.
     pub fn extend<I: Identifier, T: IntoIterator<Item = I>>(&mut self, ids: T) -> &mut Self {
-        let mut id_iter = ids.into_iter();
-
-        // if the id list is empty, write the first identifier without a comma
-        if self.buffer.is_empty() {
-            match id_iter.next() {
-                Some(first) => {
-                    first.write_identifier(self.buffer);
-                }
-                None => return self,
-            }
+        for id in ids {
+            self.push(&id);
         }
-
-        for id in id_iter {
-            id.write_identifier(self.buffer);
-        }
-
         self
     }
 
@@ -160,6 +187,7 @@ pub struct Query {
     pagination: Option<(u16, u16)>,
     sort: Option<(SortBy, SortOrder)>,
     http: bool,
+    base_url: Option<Url>,
 }
 
 impl Query {
@@ -178,15 +206,70 @@ impl Query {
     /// Returns a [`Url`] representing the arXiv API request.
     #[must_use]
     pub fn url(&self) -> Url {
-        let mut url = Url::parse("https://export.arxiv.org/api/query").unwrap();
+        let mut url = self.endpoint();
+        self.append_pairs(&mut url.query_pairs_mut());
+        url
+    }
+
+    /// Returns the [`Url`] of the arXiv API endpoint, without any query parameters.
+    ///
+    /// This is the target URL when sending the query as a `POST` request with the
+    /// [`form_body`](Self::form_body). The endpoint is the `/api/query` path relative to the
+    /// [base URL](Self::base_url).
+    #[must_use]
+    pub fn endpoint(&self) -> Url {
+        let mut url = match &self.base_url {
+            Some(base) => {
+                let mut url = base.clone();
+                // the base URL is validated to have a path in `Query::base_url`
+                url.path_segments_mut()
+                    .unwrap()
+                    .pop_if_empty()
+                    .extend(["api", "query"]);
+                url
+            }
+            None => Url::parse("https://export.arxiv.org/api/query").unwrap(),
+        };
 
         // set scheme
         if self.http {
             let _ = url.set_scheme("http");
         }
 
-        let mut query_pairs = url.query_pairs_mut();
+        url
+    }
+
+    /// Returns the query parameters encoded as an `application/x-www-form-urlencoded` body.
+    ///
+    /// The arXiv API also accepts queries as `POST` requests to the [`endpoint`](Self::endpoint),
+    /// which avoids length limits on the URL when requesting long identifier lists. The body
+    /// contains exactly the parameters which are encoded in the [`url`](Self::url).
+    ///
+    /// ### Example
+    /// ```
+    /// use rsxiv::{id::ArticleId, query::Query};
+    ///
+    /// let mut query = Query::new();
+    /// query
+    ///     .id_list()
+    ///     .push(&ArticleId::parse("2301.00001").unwrap())
+    ///     .push(&ArticleId::parse("hep-th/0309013v1").unwrap());
+    ///
+    /// assert_eq!(query.endpoint().as_str(), "https://export.arxiv.org/api/query");
+    /// assert_eq!(query.form_body(), "id_list=2301.00001%2Chep-th%2F0309013v1");
+    /// ```
+    #[must_use]
+    pub fn form_body(&self) -> String {
+        let mut serializer = form_urlencoded::Serializer::new(String::new());
+        self.append_pairs(&mut serializer);
+        serializer.finish()
+    }
 
+    /// Append the query parameters to the serializer.
+    fn append_pairs<T: form_urlencoded::Target>(
+        &self,
+        query_pairs: &mut form_urlencoded::Serializer<'_, T>,
+    ) {
         // set search queries
         if !self.search_query.is_empty() {
             query_pairs.append_pair("search_query", &self.search_query);
@@ -223,10 +306,116 @@ impl Query {
             };
             query_pairs.append_pair("sortOrder", s);
         }
+    }
 
-        drop(query_pairs);
+    /// Rewrite the query in a canonical form, so that queries which request the same results
+    /// produce the same [`url`](Self::url).
+    ///
+    /// The canonical form is obtained as follows:
+    /// - The identifier list is sorted in the [order of the identifiers](ArticleId#ordering), and
+    ///   duplicate identifiers are removed. Identifiers with different versions are not considered
+    ///   duplicates, since they correspond to different entries in the response.
+    ///   Identifiers which cannot be parsed are kept verbatim, sorted, after the other
+    ///   identifiers.
+    /// - Pagination is removed if it is equal to the arXiv default (`start=0` and
+    ///   `max_results=10`).
+    /// - The sort order is removed if it is equal to the arXiv default (sorted by relevance in
+    ///   descending order).
+    ///
+    /// ### Example
+    /// ```
+    /// use rsxiv::{
+    ///     id::{ArticleId, Validated},
+    ///     query::{Query, SortBy, SortOrder},
+    /// };
+    ///
+    /// let mut query = Query::new();
+    /// query
+    ///     .sort(SortBy::Relevance, SortOrder::Descending)
+    ///     .id_list()
+    ///     .push(&ArticleId::parse("2301.00001").unwrap())
+    ///     .push(&Validated::parse("math.CA/0309136").unwrap())
+    ///     .push(&ArticleId::parse("2301.00001").unwrap());
+    ///
+    /// query.canonicalize();
+    ///
+    /// assert_eq!(
+    ///     query.url().as_str(),
+    ///     "https://export.arxiv.org/api/query?id_list=math%2F0309136%2C2301.00001"
+    /// );
+    /// ```
+    pub fn canonicalize(&mut self) -> &mut Self {
+        if !self.id_list.is_empty() {
+            // the identifier list may contain identifiers which cannot be parsed, for instance if
+            // the query was deserialized; these are kept verbatim so that distinct queries never
+            // have the same canonical form
+            let mut ids = Vec::new();
+            let mut raw = Vec::new();
+            for id in self.id_list.split(',') {
+                match ArticleId::parse(id) {
+                    Ok(parsed) => ids.push(parsed),
+                    Err(_) => raw.push(id.to_owned()),
+                }
+            }
+            ids.sort_unstable();
+            ids.dedup();
+            raw.sort_unstable();
+            raw.dedup();
+
+            self.id_list.clear();
+            self.id_list().extend(ids);
+            for id in raw {
+                if !self.id_list.is_empty() {
+                    self.id_list.push(',');
+                }
+                self.id_list.push_str(&id);
+            }
+        }
 
-        url
+        if self.pagination == Some((0, 10)) {
+            self.pagination = None;
+        }
+
+        if matches!(self.sort, Some((SortBy::Relevance, SortOrder::Descending))) {
+            self.sort = None;
+        }
+
+        self
+    }
+
+    /// Returns a stable key identifying the request, suitable for caching responses.
+    ///
+    /// The key is the [`url`](Self::url) of the [canonical form](Self::canonicalize) of the query,
+    /// so queries which request the same results have the same key.
+    /// ```
+    /// use rsxiv::{id::ArticleId, query::Query};
+    ///
+    /// let first = ArticleId::parse("2301.00001").unwrap();
+    /// let second = ArticleId::parse("2301.00002").unwrap();
+    ///
+    /// let mut query = Query::new();
+    /// query.id_list().push(&first).push(&second);
+    ///
+    /// let mut reordered = Query::new();
+    /// reordered.paginate(0, 10).unwrap().id_list().push(&second).push(&first);
+    ///
+    /// assert_eq!(query.cache_key(), reordered.cache_key());
+    /// assert_eq!(query.cache_hash(), reordered.cache_hash());
+    /// ```
+    #[must_use]
+    pub fn cache_key(&self) -> String {
+        let mut canonical = self.clone();
+        canonical.canonicalize();
+        canonical.url().into()
+    }
+
+    /// Returns a stable 64-bit hash of the [`cache_key`](Self::cache_key).
+    ///
+    /// The hash is computed with the 64-bit FNV-1a hash function, and is guaranteed to remain
+    /// unchanged for major versions of this crate.
+    #[must_use]
+    pub fn cache_hash(&self) -> u64 {
+        fnv1a(self.cache_key().as_bytes())
     }
 
     /// Use `http://` protocol.
@@ -236,11 +425,76 @@ impl Query {
     }
 
     /// Use `https://` protocol (default).
+    ///
+    /// If a [base URL](Self::base_url) is set, the protocol of the base URL is used instead.
     pub fn https(&mut self) -> &mut Self {
         self.http = false;
         self
     }
 
+    /// Send the query to a different server, such as a mirror, a caching proxy, or a local mock
+    /// server.
+    ///
+    /// The requests are sent to the `/api/query` path relative to the base URL, with the same
+    /// query parameters. The default base URL is `https://export.arxiv.org/`.
+    ///
+    /// Returns `None` if the base URL does not use the `http` or `https` protocol, or if it has a
+    /// query or fragment, in which case the base URL is not updated.
+    ///
+    /// ### Example
+    /// ```
+    /// use rsxiv::{id::ArticleId, query::Query};
+    /// use url::Url;
+    ///
+    /// let mut query = Query::new();
+    /// query
+    ///     .base_url(Url::parse("http://localhost:8080").unwrap())
+    ///     .unwrap()
+    ///     .id_list()
+    ///     .push(&ArticleId::parse("2301.00001").unwrap());
+    /// assert_eq!(
+    ///     query.url().as_str(),
+    ///     "http://localhost:8080/api/query?id_list=2301.00001"
+    /// );
+    ///
+    /// // the path of the base URL is preserved
+    /// query
+    ///     .base_url(Url::parse("https://proxy.example.edu/arxiv").unwrap())
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     query.endpoint().as_str(),
+    ///     "https://proxy.example.edu/arxiv/api/query"
+    /// );
+    ///
+    /// assert!(query.base_url(Url::parse("ftp://localhost").unwrap()).is_none());
+    /// assert!(query.base_url(Url::parse("http://localhost/?a=b").unwrap()).is_none());
+    /// ```
+    pub fn base_url(&mut self, base_url: Url) -> Option<&mut Self> {
+        if matches!(base_url.scheme(), "http" | "https")
+            && !base_url.cannot_be_a_base()
+            && base_url.query().is_none()
+            && base_url.fragment().is_none()
+        {
+            self.base_url = Some(base_url);
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    /// Send the query to the default arXiv API server, removing any custom
+    /// [base URL](Self::base_url).
+    pub fn default_base_url(&mut self) -> &mut Self {
+        self.base_url = None;
+        self
+    }
+
+    /// The `start` and `max_results` parameters of the query, if set.
+    #[cfg(feature = "async")]
+    pub(crate) fn pagination(&self) -> Option<(u16, u16)> {
+        self.pagination
+    }
+
     /// Returns a handle to modify the search parameters.
     ///
     /// See the [`SearchQuery`] documentation for examples and more detail.
@@ -250,6 +504,13 @@ impl Query {
         }
     }
 
+    /// Returns the structured representation of the search query, or `None` if the search query is
+    /// empty.
+    #[must_use]
+    pub fn search_expr(&self) -> Option<Expr> {
+        Expr::parse(&self.search_query)
+    }
+
     /// Returns a handle to modify the identifier list.
     pub fn id_list(&mut self) -> IdList<'_> {
         IdList {
@@ -261,13 +522,14 @@ impl Query {
     /// `max_results` results.
     ///
     /// This method returns `None` if `start > 30000` or `max_results > 2000`, in which case
-    /// the pagination will not be updated.
+    /// the pagination will not be updated. In order to retrieve more results than permitted by
+    /// these limits, use a [`PagePlanner`].
     ///
     /// Corresponds to [§3.1.1.2 of the API manual][api].
     ///
     /// [api]: https://info.arxiv.org/help/api/user-manual.html#3112-start-and-max_results-paging
     pub fn paginate(&mut self, start: u16, max_results: u16) -> Option<&mut Self> {
-        if start <= 30000 && max_results <= 2000 {
+        if start <= MAX_START && max_results <= MAX_RESULTS {
             self.pagination = Some((start, max_results));
             Some(self)
         } else {
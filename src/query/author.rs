@@ -0,0 +1,152 @@
+use super::Field;
+use crate::response::AuthorName;
+
+/// How closely an author search field built by [`Field::from_author`] should match the name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AuthorMatch {
+    /// Match the keyname only, like `au:del_maestro`.
+    Surname,
+    /// Match the keyname and the first initial, like `au:del_maestro_a`.
+    #[default]
+    SurnameInitial,
+    /// Match the keyname, every first name, and the suffix, like `au:del_maestro_adrian`.
+    Exact,
+}
+
+impl Field<String> {
+    /// Construct an author search field from a parsed author name.
+    ///
+    /// The arXiv author search expects the keyname first, with the components of the name joined
+    /// by underscores. The name is normalized by folding diacritics to ASCII, converting to
+    /// lowercase, and removing punctuation other than `-`. The [`AuthorMatch`] determines which
+    /// components of the name are included.
+    ///
+    /// Returns `None` if the keyname is empty after normalization.
+    ///
+    /// ### Example
+    /// ```
+    /// use rsxiv::{
+    ///     query::{AuthorMatch, Field},
+    ///     response::AuthorName,
+    /// };
+    ///
+    /// let name = AuthorName::from_arxiv("Adrián del Maestro");
+    /// assert_eq!(
+    ///     Field::from_author(&name, AuthorMatch::Surname).unwrap().to_string(),
+    ///     "au:del_maestro"
+    /// );
+    /// assert_eq!(
+    ///     Field::from_author(&name, AuthorMatch::SurnameInitial).unwrap().to_string(),
+    ///     "au:del_maestro_a"
+    /// );
+    /// assert_eq!(
+    ///     Field::from_author(&name, AuthorMatch::Exact).unwrap().to_string(),
+    ///     "au:del_maestro_adrian"
+    /// );
+    ///
+    /// let name = AuthorName::from_arxiv("J. Ø. Øksendal Jr.");
+    /// assert_eq!(
+    ///     Field::from_author(&name, AuthorMatch::Exact).unwrap().to_string(),
+    ///     "au:oksendal_j_o_jr"
+    /// );
+    /// ```
+    pub fn from_author(name: &AuthorName, mode: AuthorMatch) -> Option<Self> {
+        let mut value = String::new();
+        push_normalized(&mut value, &name.keyname);
+        if value.is_empty() {
+            return None;
+        }
+
+        match mode {
+            AuthorMatch::Surname => {}
+            AuthorMatch::SurnameInitial => {
+                let mut initial = String::new();
+                push_normalized(&mut initial, &name.firstnames);
+                if let Some(c) = initial.chars().find(char::is_ascii_alphanumeric) {
+                    value.push('_');
+                    value.push(c);
+                }
+            }
+            AuthorMatch::Exact => {
+                for part in [&name.firstnames, &name.suffix] {
+                    let len = value.len();
+                    value.push('_');
+                    push_normalized(&mut value, part);
+                    if value.len() == len + 1 {
+                        value.truncate(len);
+                    }
+                }
+            }
+        }
+
+        Self::au(value)
+    }
+}
+
+/// Append the normalized form of a name component to the buffer.
+///
+/// Whitespace-separated words are joined by underscores, and words which are empty after
+/// normalization are skipped.
+pub(super) fn push_normalized(buffer: &mut String, component: &str) {
+    let mut first = true;
+    for word in component.split_whitespace() {
+        let start = buffer.len();
+        if !first {
+            buffer.push('_');
+        }
+
+        let word_start = buffer.len();
+        for c in word.chars() {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                buffer.push(c.to_ascii_lowercase());
+            } else if let Some(folded) = fold(c) {
+                buffer.push_str(folded);
+            }
+        }
+
+        if buffer.len() == word_start {
+            buffer.truncate(start);
+        } else {
+            first = false;
+        }
+    }
+}
+
+/// Fold a non-ASCII letter to a lowercase ASCII approximation.
+///
+/// Returns `None` for characters which should be dropped, such as combining diacritical marks
+/// and characters with no ASCII approximation.
+pub(super) fn fold(c: char) -> Option<&'static str> {
+    Some(match c {
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' | 'Ă' | 'Ą' | 'à' | 'á' | 'â' | 'ã' | 'ä' | 'å'
+        | 'ā' | 'ă' | 'ą' => "a",
+        'Æ' | 'æ' => "ae",
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' | 'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'Ď' | 'Đ' | 'Ð' | 'ď' | 'đ' | 'ð' => "d",
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' | 'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ'
+        | 'ė' | 'ę' | 'ě' => "e",
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' | 'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'Ĥ' | 'Ħ' | 'ĥ' | 'ħ' => "h",
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' | 'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī'
+        | 'ĭ' | 'į' | 'ı' => "i",
+        'Ĳ' | 'ĳ' => "ij",
+        'Ĵ' | 'ĵ' => "j",
+        'Ķ' | 'ķ' | 'ĸ' => "k",
+        'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł' | 'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'Ñ' | 'Ń' | 'Ņ' | 'Ň' | 'Ŋ' | 'ñ' | 'ń' | 'ņ' | 'ň' | 'ŉ' | 'ŋ' => "n",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' | 'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø'
+        | 'ō' | 'ŏ' | 'ő' => "o",
+        'Œ' | 'œ' => "oe",
+        'Ŕ' | 'Ŗ' | 'Ř' | 'ŕ' | 'ŗ' | 'ř' => "r",
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' | 'Ș' | 'ś' | 'ŝ' | 'ş' | 'š' | 'ș' | 'ſ' => "s",
+        'ß' => "ss",
+        'Ţ' | 'Ť' | 'Ŧ' | 'Ț' | 'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+        'Þ' | 'þ' => "th",
+        'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' | 'ù' | 'ú' | 'û' | 'ü' | 'ũ'
+        | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'Ŵ' | 'ŵ' => "w",
+        'Ý' | 'Ÿ' | 'Ŷ' | 'ý' | 'ÿ' | 'ŷ' => "y",
+        'Ź' | 'Ż' | 'Ž' | 'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    })
+}
@@ -0,0 +1,146 @@
+use url::form_urlencoded::byte_serialize;
+
+use super::{MAX_RESULTS, Query};
+use crate::id::Identifier;
+
+/// The default maximum length of a query URL generated by a [`QueryBatcher`].
+const DEFAULT_MAX_URL_LEN: usize = 4096;
+
+/// The length of the URL-encoded `,` separating identifiers in the `id_list`.
+const ENCODED_SEPARATOR_LEN: usize = "%2C".len();
+
+/// An iterator which splits a list of identifiers into a sequence of [`Query`]s.
+///
+/// Each query requests a batch of identifiers in its [`id_list`](Query::id_list) and is
+/// [paginated](Query::paginate) so that every entry in the batch is returned by a single request.
+/// The batches are chosen so that the [URL](Query::url) of each query is at most
+/// [`max_url_len`](Self::max_url_len) bytes, and each batch contains at most
+/// [`max_ids`](Self::max_ids) identifiers.
+///
+/// By default, a batch contains at most `2000` identifiers (the largest page size permitted by the
+/// arXiv API) and the URL length is at most `4096` bytes.
+///
+/// ### Example
+/// ```
+/// use std::num::NonZero;
+///
+/// use rsxiv::{id::ArticleId, query::QueryBatcher};
+///
+/// let ids = (1..=500)
+///     .map(|n| ArticleId::new(2023, 1, None, NonZero::new(n).unwrap(), None).unwrap());
+///
+/// let mut total = 0;
+/// for query in QueryBatcher::new(ids).max_ids(200) {
+///     assert!(query.url().as_str().len() <= 4096);
+///     total += 1;
+/// }
+/// assert_eq!(total, 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct QueryBatcher<I> {
+    ids: I,
+    pending: Option<String>,
+    template: Query,
+    max_ids: u16,
+    max_url_len: usize,
+}
+
+impl<I> QueryBatcher<I>
+where
+    I: Iterator,
+    I::Item: Identifier,
+{
+    /// Construct a new batcher from a list of identifiers.
+    pub fn new<T: IntoIterator<IntoIter = I>>(ids: T) -> Self {
+        Self {
+            ids: ids.into_iter(),
+            pending: None,
+            template: Query::new(),
+            max_ids: MAX_RESULTS,
+            max_url_len: DEFAULT_MAX_URL_LEN,
+        }
+    }
+
+    /// Use the provided query as a template for every generated query.
+    ///
+    /// The search query, sort order, and protocol of the template are preserved. The identifier
+    /// list and pagination are overwritten.
+    #[must_use]
+    pub fn template(mut self, query: Query) -> Self {
+        self.template = query;
+        self
+    }
+
+    /// Set the maximum number of identifiers in each query.
+    ///
+    /// The value is clamped to the range `1..=2000`.
+    #[must_use]
+    pub fn max_ids(mut self, max_ids: u16) -> Self {
+        self.max_ids = max_ids.clamp(1, MAX_RESULTS);
+        self
+    }
+
+    /// Set the maximum length of the URL of each query.
+    ///
+    /// A query always contains at least one identifier, so the URL may exceed this length if a
+    /// single identifier does not fit.
+    #[must_use]
+    pub fn max_url_len(mut self, max_url_len: usize) -> Self {
+        self.max_url_len = max_url_len;
+        self
+    }
+
+    /// The length of the URL without an `id_list`, including the separator and key of the
+    /// `id_list` parameter.
+    fn base_url_len(&self) -> usize {
+        let mut base = self.template.clone();
+        base.id_list.clear();
+        base.pagination = Some((0, self.max_ids));
+        base.url().as_str().len() + "&id_list=".len()
+    }
+}
+
+/// The length of the identifier after URL encoding.
+fn encoded_len(id: &str) -> usize {
+    byte_serialize(id.as_bytes()).map(str::len).sum()
+}
+
+impl<I> Iterator for QueryBatcher<I>
+where
+    I: Iterator,
+    I::Item: Identifier,
+{
+    type Item = Query;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut id_list = match self.pending.take() {
+            Some(id) => id,
+            None => self.ids.next()?.identifier().into_owned(),
+        };
+        let mut url_len = self.base_url_len() + encoded_len(&id_list);
+        let mut count = 1;
+
+        while count < self.max_ids {
+            let Some(id) = self.ids.next() else {
+                break;
+            };
+            let id = id.identifier();
+
+            let new_len = url_len + ENCODED_SEPARATOR_LEN + encoded_len(&id);
+            if new_len > self.max_url_len {
+                self.pending = Some(id.into_owned());
+                break;
+            }
+
+            id_list.push(',');
+            id_list.push_str(&id);
+            url_len = new_len;
+            count += 1;
+        }
+
+        let mut query = self.template.clone();
+        query.id_list = id_list;
+        query.pagination = Some((0, count));
+        Some(query)
+    }
+}
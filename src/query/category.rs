@@ -0,0 +1,259 @@
+use std::fmt::Display;
+
+/// The arXiv category taxonomy, in sorted order.
+///
+/// This includes the archives which were discontinued or subsumed into other archives, since
+/// they can still appear as categories of older articles. This is the list in `src/id/find.rs`.
+static CATEGORIES: &[&str] = &[
+    "acc-phys",
+    "adap-org",
+    "alg-geom",
+    "ao-sci",
+    "astro-ph",
+    "astro-ph.CO",
+    "astro-ph.EP",
+    "astro-ph.GA",
+    "astro-ph.HE",
+    "astro-ph.IM",
+    "astro-ph.SR",
+    "atom-ph",
+    "bayes-an",
+    "chao-dyn",
+    "chem-ph",
+    "cmp-lg",
+    "comp-gas",
+    "cond-mat",
+    "cond-mat.dis-nn",
+    "cond-mat.mes-hall",
+    "cond-mat.mtrl-sci",
+    "cond-mat.other",
+    "cond-mat.quant-gas",
+    "cond-mat.soft",
+    "cond-mat.stat-mech",
+    "cond-mat.str-el",
+    "cond-mat.supr-con",
+    "cs.AI",
+    "cs.AR",
+    "cs.CC",
+    "cs.CE",
+    "cs.CG",
+    "cs.CL",
+    "cs.CR",
+    "cs.CV",
+    "cs.CY",
+    "cs.DB",
+    "cs.DC",
+    "cs.DL",
+    "cs.DM",
+    "cs.DS",
+    "cs.ET",
+    "cs.FL",
+    "cs.GL",
+    "cs.GR",
+    "cs.GT",
+    "cs.HC",
+    "cs.IR",
+    "cs.IT",
+    "cs.LG",
+    "cs.LO",
+    "cs.MA",
+    "cs.MM",
+    "cs.MS",
+    "cs.NA",
+    "cs.NE",
+    "cs.NI",
+    "cs.OH",
+    "cs.OS",
+    "cs.PF",
+    "cs.PL",
+    "cs.RO",
+    "cs.SC",
+    "cs.SD",
+    "cs.SE",
+    "cs.SI",
+    "cs.SY",
+    "dg-ga",
+    "econ.EM",
+    "econ.GN",
+    "econ.TH",
+    "eess.AS",
+    "eess.IV",
+    "eess.SP",
+    "eess.SY",
+    "funct-an",
+    "gr-qc",
+    "hep-ex",
+    "hep-lat",
+    "hep-ph",
+    "hep-th",
+    "math-ph",
+    "math.AC",
+    "math.AG",
+    "math.AP",
+    "math.AT",
+    "math.CA",
+    "math.CO",
+    "math.CT",
+    "math.CV",
+    "math.DG",
+    "math.DS",
+    "math.FA",
+    "math.GM",
+    "math.GN",
+    "math.GR",
+    "math.GT",
+    "math.HO",
+    "math.IT",
+    "math.KT",
+    "math.LO",
+    "math.MG",
+    "math.MP",
+    "math.NA",
+    "math.NT",
+    "math.OA",
+    "math.OC",
+    "math.PR",
+    "math.QA",
+    "math.RA",
+    "math.RT",
+    "math.SG",
+    "math.SP",
+    "math.ST",
+    "mtrl-th",
+    "nlin.AO",
+    "nlin.CD",
+    "nlin.CG",
+    "nlin.PS",
+    "nlin.SI",
+    "nucl-ex",
+    "nucl-th",
+    "patt-sol",
+    "physics.acc-ph",
+    "physics.ao-ph",
+    "physics.app-ph",
+    "physics.atm-clus",
+    "physics.atom-ph",
+    "physics.bio-ph",
+    "physics.chem-ph",
+    "physics.class-ph",
+    "physics.comp-ph",
+    "physics.data-an",
+    "physics.ed-ph",
+    "physics.flu-dyn",
+    "physics.gen-ph",
+    "physics.geo-ph",
+    "physics.hist-ph",
+    "physics.ins-det",
+    "physics.med-ph",
+    "physics.optics",
+    "physics.plasm-ph",
+    "physics.pop-ph",
+    "physics.soc-ph",
+    "physics.space-ph",
+    "plasm-ph",
+    "q-alg",
+    "q-bio",
+    "q-bio.BM",
+    "q-bio.CB",
+    "q-bio.GN",
+    "q-bio.MN",
+    "q-bio.NC",
+    "q-bio.OT",
+    "q-bio.PE",
+    "q-bio.QM",
+    "q-bio.SC",
+    "q-bio.TO",
+    "q-fin.CP",
+    "q-fin.EC",
+    "q-fin.GN",
+    "q-fin.MF",
+    "q-fin.PM",
+    "q-fin.PR",
+    "q-fin.RM",
+    "q-fin.ST",
+    "q-fin.TR",
+    "quant-ph",
+    "solv-int",
+    "stat.AP",
+    "stat.CO",
+    "stat.ME",
+    "stat.ML",
+    "stat.OT",
+    "stat.TH",
+    "supr-con",
+];
+
+/// A subject category in the arXiv category taxonomy, such as `math.CA` or `hep-th`.
+///
+/// ### Example
+/// ```
+/// use rsxiv::query::Category;
+///
+/// let category = Category::from_id("math.CA").unwrap();
+/// assert_eq!(category.as_str(), "math.CA");
+/// assert_eq!(category.archive(), "math");
+///
+/// // categories are case-sensitive
+/// assert!(Category::from_id("math.Ca").is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Category(u16);
+
+impl Category {
+    /// Read a category from its identifier, returning `None` if the category is not in the
+    /// taxonomy.
+    #[must_use]
+    pub fn from_id(id: &str) -> Option<Self> {
+        CATEGORIES
+            .binary_search(&id)
+            .ok()
+            .map(|idx| Self(idx as u16))
+    }
+
+    /// The category identifier, as used in search queries.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        CATEGORIES[self.0 as usize]
+    }
+
+    /// The archive containing the category; that is, the part of the identifier before the
+    /// `.`, if any.
+    /// ```
+    /// use rsxiv::query::Category;
+    ///
+    /// assert_eq!(Category::from_id("cond-mat.str-el").unwrap().archive(), "cond-mat");
+    /// assert_eq!(Category::from_id("hep-th").unwrap().archive(), "hep-th");
+    /// ```
+    #[must_use]
+    pub fn archive(&self) -> &'static str {
+        let id = self.as_str();
+        id.split_once('.').map_or(id, |(archive, _)| archive)
+    }
+
+    /// Returns an iterator over every category in the taxonomy, in sorted order.
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..CATEGORIES.len() as u16).map(Self)
+    }
+
+    /// Returns an iterator over the categories in the provided archive, in sorted order.
+    ///
+    /// This includes the archive itself, if it is also a category.
+    /// ```
+    /// use rsxiv::query::Category;
+    ///
+    /// assert_eq!(Category::in_archive("econ").map(|c| c.as_str()).collect::<Vec<_>>(), [
+    ///     "econ.EM", "econ.GN", "econ.TH"
+    /// ]);
+    /// // `math-ph` is a different archive
+    /// assert!(Category::in_archive("math").all(|c| c.as_str().starts_with("math.")));
+    /// ```
+    pub fn in_archive(archive: &str) -> impl Iterator<Item = Self> {
+        Self::all().filter(move |category| category.archive() == archive)
+    }
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
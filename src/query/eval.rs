@@ -0,0 +1,244 @@
+use std::ops::Range;
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Timelike};
+
+use super::{
+    AuthorMatch, BooleanOp, Expr, Field, FieldType, Query, Term,
+    author::{fold, push_normalized},
+};
+use crate::{id::ArticleId, response::Entry};
+
+impl Expr {
+    /// Returns if the entry matches the expression, approximating the arXiv search engine.
+    ///
+    /// The operators are applied from left to right. The fields of the entry are matched as
+    /// follows:
+    ///
+    /// - Text fields (`ti`, `abs`, `co` and `jr`) are split into words at non-alphanumeric
+    ///   characters, and words are compared ignoring case and diacritics. An unquoted value matches
+    ///   if every word of the value appears in the field, and a quoted value like `"dark matter"`
+    ///   matches if the words appear consecutively. A word ending in `*` matches any word with the
+    ///   prefix.
+    /// - Author fields (`au`) are normalized as in [`Field::from_author`], and match an author if
+    ///   every word of the value is a prefix of a word in the name of the author, so that
+    ///   `au:del_maestro_a` matches `Adrián del Maestro`.
+    /// - Category fields (`cat`) match any category of the entry exactly.
+    /// - Report number fields (`rn`) never match, since report numbers are not included in arXiv
+    ///   API responses.
+    /// - The `all` field matches if any of the above fields match.
+    /// - `submittedDate` ranges are compared with the `published` date of the entry, and
+    ///   `lastUpdatedDate` ranges with the `updated` date, inclusive at minute granularity.
+    ///
+    /// ### Example
+    /// ```
+    /// use rsxiv::{
+    ///     id::ArticleId,
+    ///     query::{Combine, Field, Query},
+    ///     response::parse,
+    /// };
+    ///
+    /// // abridged arXiv response
+    /// let xml = // r#"<feed xmlns...
+    #[doc = include_str!("../response/tests/query_doc.txt")]
+    /// let response = parse(xml.as_bytes()).unwrap();
+    /// let entry = &response.entries[0];
+    ///
+    /// let mut query = Query::new();
+    /// query
+    ///     .search_query()
+    ///     .init(Field::ti("\"assouad spectra\"").unwrap())
+    ///     .and(Field::au("rutar_a").unwrap())
+    ///     .and_not(Field::cat("math.NT").unwrap());
+    /// assert!(query.search_expr().unwrap().matches(entry));
+    ///
+    /// let mut query = Query::new();
+    /// query.search_query().init(Field::abs("holder").unwrap());
+    /// assert!(query.search_expr().unwrap().matches(entry));
+    /// ```
+    #[must_use]
+    pub fn matches(&self, entry: &Entry<'_>) -> bool {
+        self.tail
+            .iter()
+            .fold(self.head.matches(entry), |acc, (op, term)| match op {
+                BooleanOp::And => acc && term.matches(entry),
+                BooleanOp::Or => acc || term.matches(entry),
+                BooleanOp::AndNot => acc && !term.matches(entry),
+            })
+    }
+}
+
+impl Term {
+    /// Returns if the entry matches the term.
+    ///
+    /// See [`Expr::matches`] for more detail.
+    #[must_use]
+    pub fn matches(&self, entry: &Entry<'_>) -> bool {
+        match self {
+            Term::Field(field) => field.matches(entry),
+            Term::SubmittedDate(range) => in_range(range, &entry.published),
+            Term::LastUpdatedDate(range) => in_range(range, &entry.updated),
+            Term::Group(expr) => expr.matches(entry),
+        }
+    }
+}
+
+impl<S: AsRef<str>> Field<S> {
+    /// Returns if the entry matches the search field.
+    ///
+    /// See [`Expr::matches`] for more detail.
+    #[must_use]
+    pub fn matches(&self, entry: &Entry<'_>) -> bool {
+        field_matches(self.field_type(), self.value(), entry)
+    }
+}
+
+impl Query {
+    /// Returns if the entry would be returned by the query, approximating the arXiv search
+    /// engine.
+    ///
+    /// If the query has an identifier list, the entry must have one of the identifiers. An
+    /// identifier without a version matches every version. If the query has a search query, the
+    /// entry must also [match](Expr::matches) the search query. An empty query matches nothing.
+    #[must_use]
+    pub fn matches(&self, entry: &Entry<'_>) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        // the identifier list only contains identifiers written by `Identifier`
+        if !self.id_list.is_empty()
+            && !self
+                .id_list
+                .split(',')
+                .filter_map(|id| ArticleId::parse(id).ok())
+                .any(|id| {
+                    id == entry.id || (id.version().is_none() && id == entry.id.clear_version())
+                })
+        {
+            return false;
+        }
+
+        self.search_expr().is_none_or(|expr| expr.matches(entry))
+    }
+}
+
+/// Returns if the datetime is in the range, which is inclusive at minute granularity.
+fn in_range(range: &Range<NaiveDateTime>, datetime: &DateTime<FixedOffset>) -> bool {
+    let minute = datetime
+        .naive_utc()
+        .with_second(0)
+        .and_then(|dt| dt.with_nanosecond(0))
+        .expect("zero is a valid second and nanosecond");
+    range.start <= minute && minute <= range.end
+}
+
+/// Returns if the field of the entry matches the search value.
+fn field_matches(field_type: FieldType, value: &str, entry: &Entry<'_>) -> bool {
+    match field_type {
+        FieldType::Title => text_matches(value, &entry.title),
+        FieldType::Abstract => text_matches(value, &entry.summary),
+        FieldType::Comment => entry
+            .comment
+            .as_ref()
+            .is_some_and(|comment| text_matches(value, comment)),
+        FieldType::JournalReference => entry
+            .journal_ref
+            .as_ref()
+            .is_some_and(|journal_ref| text_matches(value, journal_ref)),
+        FieldType::SubjectCategory => entry.categories.iter().any(|category| category == value),
+        FieldType::Author => author_matches(value, entry),
+        // report numbers are not included in the entry
+        FieldType::ReportNumber => false,
+        FieldType::All => [
+            FieldType::Title,
+            FieldType::Abstract,
+            FieldType::Comment,
+            FieldType::JournalReference,
+            FieldType::SubjectCategory,
+            FieldType::Author,
+        ]
+        .into_iter()
+        .any(|field_type| field_matches(field_type, value, entry)),
+    }
+}
+
+/// Split text into words at non-alphanumeric characters, converted to lowercase with diacritics
+/// removed.
+///
+/// If `wildcards` is `true`, a trailing `*` is kept at the end of a word.
+fn words(text: &str, wildcards: bool) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            word.push(c.to_ascii_lowercase());
+        } else if let Some(folded) = fold(c) {
+            word.push_str(folded);
+        } else if c.is_alphanumeric() {
+            word.extend(c.to_lowercase());
+        } else {
+            if wildcards && c == '*' && !word.is_empty() {
+                word.push('*');
+            }
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Returns if a word of the search value matches a word of the text.
+fn word_matches(pattern: &str, word: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => word.starts_with(prefix),
+        None => pattern == word,
+    }
+}
+
+/// Returns if the text matches the search value.
+fn text_matches(value: &str, text: &str) -> bool {
+    let text = words(text, false);
+
+    if let Some(phrase) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        let phrase = words(phrase, true);
+        return !phrase.is_empty()
+            && text.windows(phrase.len()).any(|window| {
+                phrase
+                    .iter()
+                    .zip(window)
+                    .all(|(pattern, word)| word_matches(pattern, word))
+            });
+    }
+
+    let value = words(value, true);
+    !value.is_empty()
+        && value
+            .iter()
+            .all(|pattern| text.iter().any(|word| word_matches(pattern, word)))
+}
+
+/// Returns if any author of the entry matches the search value.
+fn author_matches(value: &str, entry: &Entry<'_>) -> bool {
+    let mut normalized = String::new();
+    push_normalized(&mut normalized, &value.replace(['_', '"'], " "));
+    let value: Vec<&str> = normalized.split('_').filter(|w| !w.is_empty()).collect();
+    if value.is_empty() {
+        return false;
+    }
+
+    entry.authors.iter().any(|author| {
+        Field::from_author(&author.name, AuthorMatch::Exact).is_some_and(|field| {
+            let name: Vec<&str> = field.value().split('_').collect();
+            value
+                .iter()
+                .all(|word| name.iter().any(|part| part.starts_with(word)))
+        })
+    })
+}
@@ -0,0 +1,186 @@
+use std::{fmt::Display, ops::Range};
+
+use chrono::NaiveDateTime;
+
+use super::{BooleanOp, Field, FieldType};
+
+/// The format of a datetime in a `submittedDate` range.
+pub(super) const DATE_FORMAT: &str = "%Y%m%d%H%M";
+
+/// A structured representation of a search query.
+///
+/// An expression is a non-empty list of [`Term`]s combined with [boolean operators](BooleanOp).
+/// The [`Display`] implementation renders the expression in the arXiv search query syntax, and
+/// [`Expr::parse`] reads an expression from the syntax generated by the builder methods of the
+/// [`query`](super) module.
+///
+/// ### Example
+/// ```
+/// use rsxiv::query::{BooleanOp, Combine, Expr, Field, Query, Term};
+///
+/// let mut query = Query::new();
+/// query
+///     .search_query()
+///     .init(Field::ti("proton").unwrap())
+///     .and_not(Field::au("Bob").unwrap());
+///
+/// let expr = query.search_expr().unwrap();
+/// assert_eq!(expr.head, Term::Field(Field::ti("proton".to_owned()).unwrap()));
+/// assert_eq!(expr.tail.len(), 1);
+/// assert_eq!(expr.tail[0].0, BooleanOp::AndNot);
+/// assert_eq!(expr.to_string(), "ti:proton ANDNOT au:Bob");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expr {
+    /// The first term in the expression.
+    pub head: Term,
+    /// The remaining terms, along with the operator used to combine each term with the preceding
+    /// terms.
+    pub tail: Vec<(BooleanOp, Term)>,
+}
+
+/// A single term in an [`Expr`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Term {
+    /// A search field, like `ti:proton`.
+    Field(Field<String>),
+    /// A constraint on the submission date, like `submittedDate:[202301010000 TO 202302010000]`.
+    SubmittedDate(Range<NaiveDateTime>),
+    /// A constraint on the last updated date, like
+    /// `lastUpdatedDate:[202301010000 TO 202302010000]`.
+    LastUpdatedDate(Range<NaiveDateTime>),
+    /// A bracketed sub-expression.
+    Group(Box<Expr>),
+}
+
+impl Expr {
+    /// Construct an expression consisting of a single term.
+    pub fn new(head: Term) -> Self {
+        Self {
+            head,
+            tail: Vec::new(),
+        }
+    }
+
+    /// Parse an expression from the arXiv search query syntax.
+    ///
+    /// Returns `None` if the string is not a valid search query. Only the syntax generated by the
+    /// builder methods of the [`query`](super) module is accepted.
+    /// ```
+    /// use rsxiv::query::Expr;
+    ///
+    /// let raw = "(ti:a OR rn:b) AND submittedDate:[201607080000 TO 202302180000]";
+    /// assert_eq!(Expr::parse(raw).unwrap().to_string(), raw);
+    ///
+    /// assert!(Expr::parse("(ti:a OR au:b").is_none());
+    /// ```
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut input = s;
+        let expr = parse_expr(&mut input)?;
+        input.is_empty().then_some(expr)
+    }
+
+    /// Returns an iterator over the terms in the expression, along with the operator preceding the
+    /// term (if any).
+    pub fn terms(&self) -> impl Iterator<Item = (Option<BooleanOp>, &Term)> {
+        std::iter::once((None, &self.head))
+            .chain(self.tail.iter().map(|(op, term)| (Some(*op), term)))
+    }
+}
+
+/// Parse an expression, stopping before an unmatched closing bracket.
+fn parse_expr(input: &mut &str) -> Option<Expr> {
+    let mut expr = Expr::new(parse_term(input)?);
+
+    while !input.is_empty() && !input.starts_with(')') {
+        let op = if let Some(rest) = input.strip_prefix(" AND ") {
+            *input = rest;
+            BooleanOp::And
+        } else if let Some(rest) = input.strip_prefix(" OR ") {
+            *input = rest;
+            BooleanOp::Or
+        } else if let Some(rest) = input.strip_prefix(" ANDNOT ") {
+            *input = rest;
+            BooleanOp::AndNot
+        } else {
+            return None;
+        };
+
+        expr.tail.push((op, parse_term(input)?));
+    }
+
+    Some(expr)
+}
+
+/// Parse a single term.
+fn parse_term(input: &mut &str) -> Option<Term> {
+    if let Some(rest) = input.strip_prefix('(') {
+        *input = rest;
+        let expr = parse_expr(input)?;
+        *input = input.strip_prefix(')')?;
+        return Some(Term::Group(Box::new(expr)));
+    }
+
+    if let Some(rest) = input.strip_prefix("submittedDate:[") {
+        return parse_range(input, rest).map(Term::SubmittedDate);
+    }
+
+    if let Some(rest) = input.strip_prefix("lastUpdatedDate:[") {
+        return parse_range(input, rest).map(Term::LastUpdatedDate);
+    }
+
+    let (prefix, rest) = input.split_once(':')?;
+    let field_type = FieldType::from_prefix(prefix)?;
+
+    // a field value cannot contain a boolean operator or a bracket
+    let len = [" AND ", " OR ", " ANDNOT ", ")"]
+        .into_iter()
+        .filter_map(|delimiter| rest.find(delimiter))
+        .min()
+        .unwrap_or(rest.len());
+    let (value, rest) = rest.split_at(len);
+    *input = rest;
+
+    Field::init(field_type, value.to_owned()).map(Term::Field)
+}
+
+/// Parse the contents of a date range following the opening bracket.
+fn parse_range<'a>(input: &mut &'a str, rest: &'a str) -> Option<Range<NaiveDateTime>> {
+    let (range, rest) = rest.split_once(']')?;
+    let (start, end) = range.split_once(" TO ")?;
+    let range = NaiveDateTime::parse_from_str(start, DATE_FORMAT).ok()?
+        ..NaiveDateTime::parse_from_str(end, DATE_FORMAT).ok()?;
+    *input = rest;
+    Some(range)
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.head.fmt(f)?;
+        for (op, term) in &self.tail {
+            write!(f, "{op}{term}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Term {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Term::Field(field) => field.fmt(f),
+            Term::SubmittedDate(range) => write!(
+                f,
+                "submittedDate:[{} TO {}]",
+                range.start.format(DATE_FORMAT),
+                range.end.format(DATE_FORMAT)
+            ),
+            Term::LastUpdatedDate(range) => write!(
+                f,
+                "lastUpdatedDate:[{} TO {}]",
+                range.start.format(DATE_FORMAT),
+                range.end.format(DATE_FORMAT)
+            ),
+            Term::Group(expr) => write!(f, "({expr})"),
+        }
+    }
+}
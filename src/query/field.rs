@@ -6,6 +6,8 @@ use std::{
 
 use chrono::NaiveDateTime;
 
+use super::{Category, Expr, Term, expr::DATE_FORMAT};
+
 /// A non-empty search query which can be extended with new components.
 pub trait Combine<E>: Display + Sized {
     /// Extend the query using the given boolean operation.
@@ -39,7 +41,7 @@ pub trait Combine<E>: Display + Sized {
 ///
 /// Used in conjuction with the [`Combine`] trait to build [`FieldGroup`]s or extend
 /// [`SearchQuery`](super::SearchQuery)s.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BooleanOp {
     /// The `AND` operator.
     And,
@@ -63,7 +65,7 @@ impl Display for BooleanOp {
 /// The possible search field types as enumerated in the [API reference][ref].
 ///
 /// [ref]: https://info.arxiv.org/help/api/user-manual.html#51-details-of-query-construction
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FieldType {
     /// Title
     Title,
@@ -102,10 +104,31 @@ impl FieldType {
             Self::All => "all",
         }
     }
+
+    /// Read the field type from the arXiv prefix string.
+    ///
+    /// ```
+    /// use rsxiv::query::FieldType;
+    /// assert_eq!(FieldType::from_prefix("abs"), Some(FieldType::Abstract));
+    /// assert_eq!(FieldType::from_prefix("title"), None);
+    /// ```
+    pub fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "ti" => Some(Self::Title),
+            "au" => Some(Self::Author),
+            "abs" => Some(Self::Abstract),
+            "co" => Some(Self::Comment),
+            "jr" => Some(Self::JournalReference),
+            "cat" => Some(Self::SubjectCategory),
+            "rn" => Some(Self::ReportNumber),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
 }
 
 /// A single search term in a search query.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Field<S> {
     field_type: FieldType,
     value: S,
@@ -131,12 +154,19 @@ macro_rules! field_impl {
 
 impl<S: AsRef<str>> Field<S> {
     fn check_value(value: &str) -> Option<()> {
+        // a value which starts or ends with an operator would merge with the operator joining it
+        // to the neighbouring term, so that the search query could not be parsed
+        const OPERATORS: [&str; 3] = [" AND", " OR", " ANDNOT"];
+
         if value.contains(" AND ")
             || value.contains(" OR ")
             || value.contains(" ANDNOT ")
             || value.contains(')')
             || value.contains('(')
             || value.contains(':')
+            || OPERATORS
+                .iter()
+                .any(|op| value.starts_with(op) || value.ends_with(op))
         {
             None
         } else {
@@ -149,23 +179,85 @@ impl<S: AsRef<str>> Field<S> {
     /// Returns `None` if the field contents are invalid, which is the case if it contains any of
     /// the following substrings:
     /// ```txt
-    /// [" AND ", " OR ", " ANDNOT ", "(", ")"]
+    /// [" AND ", " OR ", " ANDNOT ", "(", ")", ":"]
     /// ```
+    /// or if it starts or ends with any of `" AND"`, `" OR"`, or `" ANDNOT"`.
     pub fn init(field_type: FieldType, value: S) -> Option<Self> {
         Self::check_value(value.as_ref())?;
         Some(Self { field_type, value })
     }
 
+    /// The type of the field.
+    pub fn field_type(&self) -> FieldType {
+        self.field_type
+    }
+
+    /// The value of the field.
+    pub fn value(&self) -> &str {
+        self.value.as_ref()
+    }
+
     field_impl!(ti, Title);
     field_impl!(au, Author);
     field_impl!(abs, Abstract);
     field_impl!(co, Comment);
     field_impl!(jr, JournalReference);
-    field_impl!(cat, SubjectCategory);
+
+    /// A convenience function to call [`Field::init`] with [`FieldType::SubjectCategory`].
+    ///
+    /// Returns `None` if the value is not a [`Category`] in the arXiv taxonomy. Use
+    /// [`FieldGroup::cat`] to search every category in an archive.
+    /// ```
+    /// use rsxiv::query::Field;
+    ///
+    /// assert!(Field::cat("math.CA").is_some());
+    /// assert!(Field::cat("math.Ca").is_none());
+    /// ```
+    pub fn cat(value: S) -> Option<Self> {
+        Category::from_id(value.as_ref())?;
+        Self::init(FieldType::SubjectCategory, value)
+    }
     field_impl!(rn, ReportNumber);
     field_impl!(all, All);
 }
 
+/// A constraint on the date that an article was last updated.
+///
+/// A [`Range<NaiveDateTime>`] is interpreted as a constraint on the submission date. Wrap the
+/// range in a [`LastUpdated`] to constrain the last updated date instead.
+///
+/// ### Example
+/// ```
+/// use chrono::NaiveDate;
+/// use rsxiv::query::{Combine, Field, LastUpdated, Query};
+///
+/// let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+///
+/// let mut query = Query::new();
+/// query
+///     .search_query()
+///     .init(Field::cat("math.CA").unwrap())
+///     .and(LastUpdated(start..end));
+/// assert_eq!(
+///     query.search_expr().unwrap().to_string(),
+///     "cat:math.CA AND lastUpdatedDate:[202401010000 TO 202402010000]"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastUpdated(pub Range<NaiveDateTime>);
+
+impl Display for LastUpdated {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "lastUpdatedDate:[{} TO {}]",
+            self.0.start.format(DATE_FORMAT),
+            self.0.end.format(DATE_FORMAT)
+        )
+    }
+}
+
 /// An ordered collection of [`Field`]s, grouped together using brackets if necessary.
 ///
 /// ### Example
@@ -185,7 +277,83 @@ pub struct FieldGroup {
     num_fields: NonZero<usize>,
 }
 
+impl Field<&'static str> {
+    /// Construct a search field matching a typed [`Category`].
+    /// ```
+    /// use rsxiv::query::{Category, Field};
+    ///
+    /// let category = Category::from_id("hep-th").unwrap();
+    /// assert_eq!(Field::category(category).to_string(), "cat:hep-th");
+    /// ```
+    #[must_use]
+    pub fn category(category: Category) -> Self {
+        Self {
+            field_type: FieldType::SubjectCategory,
+            value: category.as_str(),
+        }
+    }
+}
+
 impl FieldGroup {
+    /// Construct a group matching a category, or every category in an archive.
+    ///
+    /// The pattern is either a category like `math.CA`, or an archive followed by a wildcard,
+    /// like `math.*` or `astro-ph*`. Since the arXiv API does not support wildcards in category
+    /// searches, an archive pattern is expanded to the categories in the archive combined with
+    /// `OR`. Note that the wildcard only matches categories in the archive: `math*` does not
+    /// match `math-ph`.
+    ///
+    /// Returns `None` if the pattern does not match any category in the taxonomy.
+    ///
+    /// ### Example
+    /// ```
+    /// use rsxiv::query::FieldGroup;
+    ///
+    /// assert_eq!(FieldGroup::cat("gr-qc").unwrap().to_string(), "cat:gr-qc");
+    /// assert_eq!(
+    ///     FieldGroup::cat("econ.*").unwrap().to_string(),
+    ///     "(cat:econ.EM OR cat:econ.GN OR cat:econ.TH)"
+    /// );
+    /// assert!(FieldGroup::cat("astro-ph*").unwrap().to_string().starts_with("(cat:astro-ph OR cat:astro-ph.CO OR "));
+    ///
+    /// assert!(FieldGroup::cat("maths.*").is_none());
+    /// assert!(FieldGroup::cat("math.Ca").is_none());
+    /// ```
+    pub fn cat(pattern: &str) -> Option<Self> {
+        let Some(archive) = pattern.strip_suffix('*') else {
+            return Category::from_id(pattern)
+                .map(|category| Self::init(Field::category(category)));
+        };
+        let archive = archive.strip_suffix('.').unwrap_or(archive);
+        Self::any_category(Category::in_archive(archive))
+    }
+
+    /// Combine the categories into a group using `OR`, returning `None` if there are no
+    /// categories.
+    pub(crate) fn any_category<I: IntoIterator<Item = Category>>(categories: I) -> Option<Self> {
+        let mut categories = categories.into_iter();
+        let mut group = Self::init(Field::category(categories.next()?));
+        for category in categories {
+            group = group.or(Field::category(category));
+        }
+        Some(group)
+    }
+
+    /// Returns the structured representation of the group.
+    ///
+    /// ```
+    /// use rsxiv::query::{Combine, Field, FieldGroup};
+    ///
+    /// let group = FieldGroup::init(Field::ti("a").unwrap()).or(Field::au("b").unwrap());
+    /// assert_eq!(group.expr().unwrap().tail.len(), 1);
+    /// ```
+    ///
+    /// Returns `None` if the group cannot be parsed, which does not happen for groups constructed
+    /// from valid fields.
+    pub fn expr(&self) -> Option<Expr> {
+        Expr::parse(&self.inner)
+    }
+
     /// Construct a new field group with an initial element.
     pub fn init<S: AsRef<str>>(initial: Field<S>) -> Self {
         let mut inner = String::new();
@@ -223,9 +391,10 @@ impl Combine<Range<NaiveDateTime>> for FieldGroup {
             &mut self.inner,
             "{}submittedDate:[{} TO {}]",
             op,
-            element.start.format("%Y%m%d%H%M"),
-            element.end.format("%Y%m%d%H%M")
+            element.start.format(DATE_FORMAT),
+            element.end.format(DATE_FORMAT)
         );
+        self.num_fields = self.num_fields.saturating_add(1);
         self
     }
 }
@@ -240,3 +409,34 @@ impl<S: AsRef<str>> From<Field<S>> for FieldGroup {
         }
     }
 }
+
+impl Combine<LastUpdated> for FieldGroup {
+    fn push(mut self, op: BooleanOp, element: LastUpdated) -> Self {
+        let _ = write!(&mut self.inner, "{op}{element}");
+        self.num_fields = self.num_fields.saturating_add(1);
+        self
+    }
+}
+
+impl From<LastUpdated> for FieldGroup {
+    fn from(element: LastUpdated) -> Self {
+        Expr::new(Term::LastUpdatedDate(element.0)).into()
+    }
+}
+
+impl From<Range<NaiveDateTime>> for FieldGroup {
+    fn from(range: Range<NaiveDateTime>) -> Self {
+        Expr::new(Term::SubmittedDate(range)).into()
+    }
+}
+
+impl From<Expr> for FieldGroup {
+    fn from(expr: Expr) -> Self {
+        let mut inner = String::new();
+        let _ = write!(&mut inner, "{expr}");
+        Self {
+            inner,
+            num_fields: NonZero::new(expr.tail.len() + 1).unwrap(),
+        }
+    }
+}
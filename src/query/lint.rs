@@ -0,0 +1,226 @@
+use std::{fmt::Display, ops::Range};
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+
+use super::{BooleanOp, Expr, Query, SortBy, Term};
+use crate::id::ArticleId;
+
+/// The severity of a [`Lint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The query is valid, but is likely a mistake.
+    Warning,
+    /// The query will be rejected by the arXiv API, or can never return any results.
+    Error,
+}
+
+/// A potential problem with a [`Query`], as reported by [`Query::lint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lint {
+    /// The query has neither a search query nor an identifier list.
+    EmptyQuery,
+    /// A `submittedDate` or `lastUpdatedDate` range has a start after its end, so it matches
+    /// nothing.
+    InvertedDateRange(Range<NaiveDateTime>),
+    /// Every search field in the search query is excluded using `ANDNOT`, so the query only
+    /// restricts the results by exclusion.
+    OnlyExclusions,
+    /// The results are sorted by relevance, which is not stable across requests, but the query
+    /// requests a page other than the first.
+    UnstablePaging,
+    /// The identifier is in the identifier list, but its submission month is excluded by a
+    /// `submittedDate` range in the search query.
+    ExcludedId(ArticleId),
+}
+
+impl Lint {
+    /// The severity of the lint.
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        match self {
+            Lint::EmptyQuery | Lint::InvertedDateRange(_) => Severity::Error,
+            Lint::OnlyExclusions | Lint::UnstablePaging | Lint::ExcludedId(_) => Severity::Warning,
+        }
+    }
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Lint::EmptyQuery => f.write_str("query has no search query or identifier list"),
+            Lint::InvertedDateRange(range) => write!(
+                f,
+                "date range starts at {} after it ends at {}",
+                range.start, range.end
+            ),
+            Lint::OnlyExclusions => {
+                f.write_str("every search field in the search query is excluded with ANDNOT")
+            }
+            Lint::UnstablePaging => {
+                f.write_str("results sorted by relevance are not stable across pages")
+            }
+            Lint::ExcludedId(id) => write!(
+                f,
+                "identifier '{id}' is excluded by a submission date range in the search query"
+            ),
+        }
+    }
+}
+
+impl Query {
+    /// Check the query for common mistakes, and for queries which will be rejected by the arXiv
+    /// API.
+    ///
+    /// Returns a list of problems, which is empty if no problems were found. The checks are
+    /// heuristic: a query with no [errors](Severity::Error) is not guaranteed to be accepted.
+    ///
+    /// ### Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use rsxiv::{
+    ///     id::ArticleId,
+    ///     query::{Combine, Field, Lint, Query, Severity},
+    /// };
+    ///
+    /// let mut query = Query::new();
+    /// assert_eq!(query.lint(), [Lint::EmptyQuery]);
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    /// query.search_query().init(Field::ti("proton").unwrap()).and(start..end);
+    /// assert!(query.lint().is_empty());
+    ///
+    /// // the identifier was submitted in 2023, so the query cannot return it
+    /// let id = ArticleId::parse("2301.00001").unwrap();
+    /// query.id_list().push(&id);
+    /// let lints = query.lint();
+    /// assert_eq!(lints, [Lint::ExcludedId(id)]);
+    /// assert_eq!(lints[0].severity(), Severity::Warning);
+    /// ```
+    #[must_use]
+    pub fn lint(&self) -> Vec<Lint> {
+        let mut lints = Vec::new();
+
+        if self.is_empty() {
+            lints.push(Lint::EmptyQuery);
+        }
+
+        let expr = self.search_expr();
+        if let Some(expr) = &expr {
+            lint_ranges(expr, &mut lints);
+
+            if has_exclusions(expr) && !has_required_field(expr) {
+                lints.push(Lint::OnlyExclusions);
+            }
+        }
+
+        if matches!(self.sort, None | Some((SortBy::Relevance, _)))
+            && self.pagination.is_some_and(|(start, _)| start > 0)
+        {
+            lints.push(Lint::UnstablePaging);
+        }
+
+        if let Some(expr) = &expr
+            && !self.id_list.is_empty()
+        {
+            lint_id_list(expr, &self.id_list, &mut lints);
+        }
+
+        lints
+    }
+}
+
+/// Report every inverted date range in the expression.
+fn lint_ranges(expr: &Expr, lints: &mut Vec<Lint>) {
+    for (_, term) in expr.terms() {
+        match term {
+            Term::SubmittedDate(range) | Term::LastUpdatedDate(range)
+                if range.start > range.end =>
+            {
+                lints.push(Lint::InvertedDateRange(range.clone()));
+            }
+            Term::Group(inner) => lint_ranges(inner, lints),
+            _ => {}
+        }
+    }
+}
+
+/// Returns if the expression contains any `ANDNOT` operator.
+fn has_exclusions(expr: &Expr) -> bool {
+    expr.terms().any(|(op, term)| {
+        op == Some(BooleanOp::AndNot) || matches!(term, Term::Group(inner) if has_exclusions(inner))
+    })
+}
+
+/// Returns if the expression contains a search field which is not excluded with `ANDNOT`.
+fn has_required_field(expr: &Expr) -> bool {
+    expr.terms()
+        .filter(|(op, _)| *op != Some(BooleanOp::AndNot))
+        .any(|(_, term)| match term {
+            Term::Field(_) => true,
+            Term::SubmittedDate(_) | Term::LastUpdatedDate(_) => false,
+            Term::Group(inner) => has_required_field(inner),
+        })
+}
+
+/// Report identifiers whose submission month cannot satisfy the top-level `submittedDate`
+/// constraints of the expression.
+///
+/// The constraints are only checked if the top-level terms are combined with `AND` and `ANDNOT`,
+/// since otherwise a date range is not required to match.
+fn lint_id_list(expr: &Expr, id_list: &str, lints: &mut Vec<Lint>) {
+    if expr.tail.iter().any(|(op, _)| *op == BooleanOp::Or) {
+        return;
+    }
+
+    let mut required = Vec::new();
+    let mut excluded = Vec::new();
+    for (op, term) in expr.terms() {
+        if let Term::SubmittedDate(range) = term {
+            if op == Some(BooleanOp::AndNot) {
+                excluded.push(range);
+            } else {
+                required.push(range);
+            }
+        }
+    }
+
+    if required.is_empty() && excluded.is_empty() {
+        return;
+    }
+
+    // the identifier list only contains identifiers written by `Identifier`
+    for id in id_list
+        .split(',')
+        .filter_map(|id| ArticleId::parse(id).ok())
+    {
+        let Some(month) = submission_month(id) else {
+            continue;
+        };
+
+        // date ranges are inclusive at minute granularity
+        let disjoint =
+            |range: &Range<NaiveDateTime>| range.end < month.start || range.start >= month.end;
+        let covers = |range: &Range<NaiveDateTime>| {
+            range.start <= month.start && month.end - TimeDelta::minutes(1) <= range.end
+        };
+
+        if required.iter().any(|range| disjoint(range))
+            || excluded.iter().any(|range| covers(range))
+        {
+            lints.push(Lint::ExcludedId(id));
+        }
+    }
+}
+
+/// The range of times in the month in which an identifier was assigned.
+fn submission_month(id: ArticleId) -> Option<Range<NaiveDateTime>> {
+    let (year, month) = (i32::from(id.year()), u32::from(id.month()));
+    let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let end = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)?
+    };
+    Some(NaiveDateTime::new(start, NaiveTime::MIN)..NaiveDateTime::new(end, NaiveTime::MIN))
+}
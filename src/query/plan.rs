@@ -0,0 +1,236 @@
+use std::{fmt::Write as _, ops::RangeInclusive};
+
+use chrono::{NaiveDateTime, TimeDelta, Timelike};
+
+use super::{MAX_RESULTS, MAX_START, Query, date_bounds, expr::DATE_FORMAT};
+use crate::response::Pagination;
+
+/// A planner which generates the page queries required to retrieve every result matching a
+/// [`Query`].
+///
+/// The arXiv API will not return results with index beyond `30000`, so a query with more matching
+/// results cannot be retrieved by [pagination](Query::paginate) alone. When a query has too many
+/// results, the planner splits it into `submittedDate` windows which each contain few enough
+/// results to be paged through completely.
+///
+/// Since the number of results in a window is only known after the window is requested, the
+/// planner must be informed of the [`Pagination`] of each response using [`PagePlanner::update`].
+/// If a window turns out to be too large, `update` returns `false`: the entries in the
+/// corresponding response should be discarded, since they will be returned again by the queries
+/// for the smaller windows.
+///
+/// ### Example
+/// ```
+/// use rsxiv::{
+///     query::{Field, PagePlanner, Query},
+///     response::Pagination,
+/// };
+///
+/// # fn send(query: &Query) -> Pagination {
+/// #     Pagination { total_results: 12000, start_index: 0, items_per_page: 2000 }
+/// # }
+/// let mut query = Query::new();
+/// query.search_query().init(Field::cat("math.CA").unwrap());
+///
+/// // the total number of results, obtained from an initial request
+/// let total_results = 45000;
+///
+/// let mut planner = PagePlanner::new(query, total_results);
+/// while let Some(page) = planner.next_query() {
+///     // make the request using `page.url()` and parse the response
+///     let pagination = send(&page);
+///
+///     if planner.update(&pagination) {
+///         // keep the entries in the response
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PagePlanner {
+    query: Query,
+    page_size: u16,
+    range: RangeInclusive<NaiveDateTime>,
+    pending: Vec<RangeInclusive<NaiveDateTime>>,
+    current: Option<Window>,
+}
+
+/// The state of the window which is currently being paged through.
+#[derive(Debug, Clone)]
+struct Window {
+    /// The inclusive `submittedDate` restriction, or `None` if the query is not restricted.
+    range: Option<RangeInclusive<NaiveDateTime>>,
+    /// The total number of results in the window, if known.
+    total: Option<u64>,
+    /// The `start` parameter of the next page.
+    next_start: u64,
+}
+
+impl PagePlanner {
+    /// Construct a new planner for the provided query, which has `total_results` results in total.
+    ///
+    /// The `total_results` can be obtained from the [`Pagination`] of any response to the query.
+    ///
+    /// If the query was paginated, the `max_results` is used as the page size. Otherwise, the page
+    /// size is set to `2000`, the largest page size permitted by the arXiv API.
+    #[must_use]
+    pub fn new(query: Query, total_results: u64) -> Self {
+        let page_size = match query.pagination {
+            Some((_, max_results)) if max_results > 0 => max_results,
+            _ => MAX_RESULTS,
+        };
+
+        let bounds = date_bounds();
+        Self {
+            query,
+            page_size,
+            range: bounds.start..=bounds.end,
+            pending: Vec::new(),
+            current: Some(Window {
+                range: None,
+                total: Some(total_results),
+                next_start: 0,
+            }),
+        }
+    }
+
+    /// Set the number of results requested by each page query.
+    ///
+    /// Returns `None` if `max_results` is `0` or exceeds `2000`, in which case the page size is not
+    /// updated.
+    pub fn page_size(&mut self, max_results: u16) -> Option<&mut Self> {
+        if max_results > 0 && max_results <= MAX_RESULTS {
+            self.page_size = max_results;
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    /// Set the range of submission dates which is split into windows if the query has too many
+    /// results.
+    ///
+    /// By default, this is the range of all possible submission dates. Narrowing the range (for
+    /// instance, by setting the end to the current date) reduces the number of requests needed
+    /// to find windows which are small enough. Results outside the range are not retrieved if the
+    /// query is split.
+    ///
+    /// Both ends of the range are inclusive. Since `submittedDate` restrictions have minute
+    /// precision, returns `None` if either end has non-zero seconds, or if the range is empty, in
+    /// which case the range is not updated.
+    pub fn date_range(&mut self, range: RangeInclusive<NaiveDateTime>) -> Option<&mut Self> {
+        let whole_minute = |date: &NaiveDateTime| date.second() == 0 && date.nanosecond() == 0;
+        if whole_minute(range.start()) && whole_minute(range.end()) && !range.is_empty() {
+            self.range = range;
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    /// The largest number of results in a window which can be retrieved by paging.
+    fn capacity(&self) -> u64 {
+        MAX_START as u64 + self.page_size as u64
+    }
+
+    /// Returns the next query to send, or `None` if every result has been planned.
+    ///
+    /// After sending the query, call [`PagePlanner::update`] with the pagination of the response.
+    pub fn next_query(&mut self) -> Option<Query> {
+        loop {
+            let window = match self.current.take() {
+                Some(window) => window,
+                None => Window {
+                    range: Some(self.pending.pop()?),
+                    total: None,
+                    next_start: 0,
+                },
+            };
+
+            if window.total.is_some_and(|total| total > self.capacity())
+                && self.split(window.range.as_ref())
+            {
+                continue;
+            }
+
+            let remaining = window.total.is_none_or(|total| window.next_start < total);
+            if remaining && window.next_start <= MAX_START as u64 {
+                let query = self.window_query(window.range.as_ref(), window.next_start as u16);
+                self.current = Some(Window {
+                    next_start: window.next_start + self.page_size as u64,
+                    ..window
+                });
+                return Some(query);
+            }
+        }
+    }
+
+    /// Update the planner with the pagination of the response to the most recent query returned by
+    /// [`PagePlanner::next_query`].
+    ///
+    /// Returns `false` if the query was too large and will be replaced by smaller queries, in which
+    /// case the entries in the response should be discarded. Otherwise, returns `true`.
+    pub fn update(&mut self, pagination: &Pagination) -> bool {
+        let capacity = self.capacity();
+        let Some(window) = &mut self.current else {
+            return true;
+        };
+
+        let first = window.total.is_none();
+        window.total = Some(pagination.total_results);
+
+        // only the first page of a window can be discarded, since the window is split before any
+        // subsequent page is requested
+        !(first
+            && pagination.total_results > capacity
+            && Self::splittable(window.range.as_ref().unwrap_or(&self.range)))
+    }
+
+    /// Returns if the range contains at least two minutes, so that it can be split into two
+    /// non-empty windows.
+    fn splittable(range: &RangeInclusive<NaiveDateTime>) -> bool {
+        (*range.end() - *range.start()).num_minutes() >= 1
+    }
+
+    /// Split the window into two halves and queue them, returning `false` if the window cannot be
+    /// split. An unrestricted window is split over the [date range](Self::date_range).
+    ///
+    /// Date ranges are inclusive at minute granularity, so the halves do not overlap.
+    fn split(&mut self, range: Option<&RangeInclusive<NaiveDateTime>>) -> bool {
+        let range = range.unwrap_or(&self.range).clone();
+        if !Self::splittable(&range) {
+            return false;
+        }
+
+        let (from, to) = range.into_inner();
+        let mid = from + TimeDelta::minutes((to - from).num_minutes() / 2);
+
+        // push in reverse order so the windows are requested in chronological order
+        self.pending.push(mid + TimeDelta::minutes(1)..=to);
+        self.pending.push(from..=mid);
+        true
+    }
+
+    /// Construct the query for the page starting at `start` in the window.
+    fn window_query(&self, range: Option<&RangeInclusive<NaiveDateTime>>, start: u16) -> Query {
+        let mut query = self.query.clone();
+
+        if let Some(range) = range {
+            let mut restriction = String::new();
+            let _ = write!(
+                &mut restriction,
+                "submittedDate:[{} TO {}]",
+                range.start().format(DATE_FORMAT),
+                range.end().format(DATE_FORMAT)
+            );
+
+            query.search_query = if query.search_query.is_empty() {
+                restriction
+            } else {
+                format!("({}) AND {restriction}", query.search_query)
+            };
+        }
+
+        query.pagination = Some((start, self.page_size));
+        query
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::NaiveDateTime;
+
+use super::{
+    AuthorMatch, Category, Combine, Field, FieldGroup, LastUpdated, Query, SortBy, SortOrder,
+    date_bounds,
+};
+use crate::{id::ArticleId, response::AuthorName};
+
+/// Preset queries for common listing tasks.
+impl Query {
+    /// A query for the articles submitted to any of the categories since the provided date,
+    /// newest first.
+    ///
+    /// Returns `None` if there are no categories.
+    ///
+    /// ### Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use rsxiv::query::{Category, Query};
+    ///
+    /// let since = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    /// let categories = ["math.CA", "math.FA"].map(|c| Category::from_id(c).unwrap());
+    ///
+    /// let query = Query::new_submissions(categories, since).unwrap();
+    /// assert_eq!(
+    ///     query.search_expr().unwrap().to_string(),
+    ///     "(cat:math.CA OR cat:math.FA) AND submittedDate:[202403010000 TO 210704010000]"
+    /// );
+    /// ```
+    pub fn new_submissions<I: IntoIterator<Item = Category>>(
+        categories: I,
+        since: NaiveDateTime,
+    ) -> Option<Self> {
+        let mut query = Self::new();
+        query
+            .sort(SortBy::SubmittedDate, SortOrder::Descending)
+            .search_query()
+            .init(FieldGroup::any_category(categories)?)
+            .and(since..date_bounds().end);
+        Some(query)
+    }
+
+    /// A query for the articles in any of the categories which were updated since the provided
+    /// date, least recently updated first.
+    ///
+    /// Sorting in ascending order means that new updates are appended to the end of the results,
+    /// so the query is suitable for incremental harvesting. Returns `None` if there are no
+    /// categories.
+    ///
+    /// ### Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use rsxiv::query::{Category, Query};
+    ///
+    /// let since = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap().and_hms_opt(9, 30, 0).unwrap();
+    /// let categories = ["math.CA", "math.FA"].map(|c| Category::from_id(c).unwrap());
+    ///
+    /// let query = Query::category_updates(categories, since).unwrap();
+    /// assert_eq!(
+    ///     query.search_expr().unwrap().to_string(),
+    ///     "(cat:math.CA OR cat:math.FA) AND lastUpdatedDate:[202403010930 TO 210704010000]"
+    /// );
+    /// ```
+    pub fn category_updates<I: IntoIterator<Item = Category>>(
+        categories: I,
+        since: NaiveDateTime,
+    ) -> Option<Self> {
+        let mut query = Self::new();
+        query
+            .sort(SortBy::LastUpdatedDate, SortOrder::Ascending)
+            .search_query()
+            .init(FieldGroup::any_category(categories)?)
+            .and(LastUpdated(since..date_bounds().end));
+        Some(query)
+    }
+
+    /// A query for the articles in the watchlist which were updated since the provided date, most
+    /// recently updated first.
+    ///
+    /// The versions of the identifiers are removed, so that the latest version of each article is
+    /// returned. Returns `None` if the watchlist is empty.
+    ///
+    /// ### Example
+    /// ```
+    /// use chrono::{NaiveDate, TimeDelta};
+    /// use rsxiv::{id::ArticleId, query::Query};
+    ///
+    /// let now = NaiveDate::from_ymd_opt(2024, 3, 8).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    /// let watchlist = ["2301.00001v2", "hep-th/9901001"].map(|id| ArticleId::parse(id).unwrap());
+    ///
+    /// // updated in the last 7 days
+    /// let query = Query::watchlist_updates(watchlist, now - TimeDelta::days(7)).unwrap();
+    /// assert_eq!(
+    ///     query.url().as_str(),
+    ///     "https://export.arxiv.org/api/query?search_query=lastUpdatedDate%3A%5B202403011200+TO+210704010000%5D&id_list=2301.00001%2Chep-th%2F9901001&sortBy=lastUpdatedDate&sortOrder=descending"
+    /// );
+    /// ```
+    pub fn watchlist_updates<I: IntoIterator<Item = ArticleId>>(
+        watchlist: I,
+        since: NaiveDateTime,
+    ) -> Option<Self> {
+        let mut query = Self::new();
+        query
+            .id_list()
+            .extend(watchlist.into_iter().map(ArticleId::clear_version));
+        if query.id_list.is_empty() {
+            return None;
+        }
+
+        query
+            .sort(SortBy::LastUpdatedDate, SortOrder::Descending)
+            .search_query()
+            .init(LastUpdated(since..date_bounds().end));
+        Some(query)
+    }
+
+    /// A query for the articles by any of the authors in any of the categories, newest first.
+    ///
+    /// The author search fields are constructed using [`Field::from_author`] with the provided
+    /// matching mode, and authors whose names cannot be converted are skipped. Returns `None` if
+    /// there are no categories or no valid authors.
+    ///
+    /// ### Example
+    /// ```
+    /// use rsxiv::{
+    ///     query::{AuthorMatch, Category, Query},
+    ///     response::AuthorName,
+    /// };
+    ///
+    /// let authors = ["Terence Tao", "Ben Green"].map(AuthorName::from_arxiv);
+    /// let categories = [Category::from_id("math.NT").unwrap()];
+    ///
+    /// let query =
+    ///     Query::authors_in_categories(&authors, AuthorMatch::SurnameInitial, categories).unwrap();
+    /// assert_eq!(
+    ///     query.search_expr().unwrap().to_string(),
+    ///     "(au:tao_t OR au:green_b) AND cat:math.NT"
+    /// );
+    /// ```
+    pub fn authors_in_categories<'a, A, C>(
+        authors: A,
+        mode: AuthorMatch,
+        categories: C,
+    ) -> Option<Self>
+    where
+        A: IntoIterator<Item = &'a AuthorName>,
+        C: IntoIterator<Item = Category>,
+    {
+        let mut authors = authors
+            .into_iter()
+            .filter_map(|name| Field::from_author(name, mode));
+        let mut author_group = FieldGroup::init(authors.next()?);
+        for author in authors {
+            author_group = author_group.or(author);
+        }
+
+        let mut query = Self::new();
+        query
+            .sort(SortBy::SubmittedDate, SortOrder::Descending)
+            .search_query()
+            .init(author_group)
+            .and(FieldGroup::any_category(categories)?);
+        Some(query)
+    }
+}
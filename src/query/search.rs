@@ -5,7 +5,7 @@ use std::{
 
 use chrono::NaiveDateTime;
 
-use crate::query::{BooleanOp, Combine, Field, FieldGroup};
+use crate::query::{BooleanOp, Combine, Field, FieldGroup, LastUpdated, expr::DATE_FORMAT};
 
 /// A handle to edit an existing search query.
 ///
@@ -150,9 +150,16 @@ impl Combine<Range<NaiveDateTime>> for NonEmptySearchQuery<'_> {
             &mut self.buffer,
             "{}submittedDate:[{} TO {}]",
             op,
-            element.start.format("%Y%m%d%H%M"),
-            element.end.format("%Y%m%d%H%M")
+            element.start.format(DATE_FORMAT),
+            element.end.format(DATE_FORMAT)
         );
         self
     }
 }
+
+impl Combine<LastUpdated> for NonEmptySearchQuery<'_> {
+    fn push(mut self, op: BooleanOp, element: LastUpdated) -> Self {
+        let _ = write!(&mut self.buffer, "{op}{element}");
+        self
+    }
+}
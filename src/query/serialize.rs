@@ -0,0 +1,677 @@
+//! # Query (de)serialization
+//!
+//! The query components are serialized in a structured form which is convenient to write by hand,
+//! for instance in a configuration file. A search query is a list of clauses, each of which is a
+//! map with an optional boolean operator `op` (which must be omitted for the first clause) and
+//! exactly one of:
+//!
+//! - `field` and `value`: a [`Field`],
+//! - `submitted_date`: a map with `start` and `end` datetimes, like `2023-01-01T00:00:00`,
+//! - `last_updated_date`: a map with `start` and `end` datetimes,
+//! - `group`: a nested list of clauses.
+//!
+//! The same validation rules as the builder methods are applied when deserializing.
+use std::{fmt, ops::Range, str::FromStr};
+
+use chrono::NaiveDateTime;
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, Error as _, MapAccess, SeqAccess, Unexpected, Visitor},
+    ser::{self, SerializeMap, SerializeSeq, SerializeStruct},
+};
+use url::Url;
+
+use super::{
+    BooleanOp, Category, Expr, Field, FieldGroup, FieldType, Query, SortBy, SortOrder, Term,
+};
+use crate::id::ArticleId;
+
+/// The format of a datetime in a serialized date range.
+const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Implement `Serialize` and `Deserialize` for a fieldless enum represented as a string.
+macro_rules! str_enum_impl {
+    ($ty:ident, $expecting:literal, { $($variant:ident => $name:literal $(| $alias:literal)*),* $(,)? }) => {
+        #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(match self {
+                    $($ty::$variant => $name,)*
+                })
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct StrVisitor;
+
+                impl Visitor<'_> for StrVisitor {
+                    type Value = $ty;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str($expecting)
+                    }
+
+                    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                        match v {
+                            $($name $(| $alias)* => Ok($ty::$variant),)*
+                            _ => Err(E::unknown_variant(v, &[$($name),*])),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_str(StrVisitor)
+            }
+        }
+    };
+}
+
+str_enum_impl!(FieldType, "a search field type", {
+    Title => "title" | "ti",
+    Author => "author" | "au",
+    Abstract => "abstract" | "abs",
+    Comment => "comment" | "co",
+    JournalReference => "journal_ref" | "jr",
+    SubjectCategory => "category" | "cat",
+    ReportNumber => "report_number" | "rn",
+    All => "all",
+});
+
+str_enum_impl!(BooleanOp, "a boolean operator", {
+    And => "AND",
+    Or => "OR",
+    AndNot => "ANDNOT",
+});
+
+str_enum_impl!(SortBy, "a sort ordering", {
+    Relevance => "relevance",
+    LastUpdatedDate => "lastUpdatedDate",
+    SubmittedDate => "submittedDate",
+});
+
+str_enum_impl!(SortOrder, "a sort direction", {
+    Ascending => "ascending",
+    Descending => "descending",
+});
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<S: AsRef<str>> Serialize for Field<S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("field", &self.field_type())?;
+        map.serialize_entry("value", self.value())?;
+        map.end()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, S: Deserialize<'de> + AsRef<str>> Deserialize<'de> for Field<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldVisitor<S>(std::marker::PhantomData<S>);
+
+        impl<'de, S: Deserialize<'de> + AsRef<str>> Visitor<'de> for FieldVisitor<S> {
+            type Value = Field<S>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map with keys `field` and `value`")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut field_type = None;
+                let mut value = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "field" => field_type = Some(map.next_value()?),
+                        "value" => value = Some(map.next_value()?),
+                        other => return Err(A::Error::unknown_field(other, &["field", "value"])),
+                    }
+                }
+                let field_type = field_type.ok_or_else(|| A::Error::missing_field("field"))?;
+                let value = value.ok_or_else(|| A::Error::missing_field("value"))?;
+                init_field(field_type, value)
+            }
+        }
+
+        deserializer.deserialize_map(FieldVisitor(std::marker::PhantomData))
+    }
+}
+
+/// Initialize a field, converting a validation failure into a deserialization error.
+///
+/// Categories are validated against the taxonomy, as in [`Field::cat`].
+fn init_field<S: AsRef<str>, E: de::Error>(field_type: FieldType, value: S) -> Result<Field<S>, E> {
+    if field_type == FieldType::SubjectCategory && Category::from_id(value.as_ref()).is_none() {
+        return Err(E::invalid_value(
+            Unexpected::Str(value.as_ref()),
+            &"a category in the arXiv taxonomy",
+        ));
+    }
+    if Field::<&str>::init(field_type, value.as_ref()).is_none() {
+        return Err(E::invalid_value(
+            Unexpected::Str(value.as_ref()),
+            &"a field value without boolean operators, brackets, or `:`",
+        ));
+    }
+    Ok(Field::init(field_type, value).unwrap())
+}
+
+/// A serialization wrapper for a date range.
+struct DateRange<'a>(&'a Range<NaiveDateTime>);
+
+impl Serialize for DateRange<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("start", &self.0.start.format(DATETIME_FORMAT).to_string())?;
+        map.serialize_entry("end", &self.0.end.format(DATETIME_FORMAT).to_string())?;
+        map.end()
+    }
+}
+
+/// A deserialization wrapper for a date range.
+struct OwnedDateRange(Range<NaiveDateTime>);
+
+impl<'de> Deserialize<'de> for OwnedDateRange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DateRangeVisitor;
+
+        impl<'de> Visitor<'de> for DateRangeVisitor {
+            type Value = OwnedDateRange;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map with keys `start` and `end`")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut start = None;
+                let mut end = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    let target = match key.as_str() {
+                        "start" => &mut start,
+                        "end" => &mut end,
+                        other => return Err(A::Error::unknown_field(other, &["start", "end"])),
+                    };
+                    let raw: String = map.next_value()?;
+                    *target = Some(NaiveDateTime::from_str(&raw).map_err(A::Error::custom)?);
+                }
+                let start = start.ok_or_else(|| A::Error::missing_field("start"))?;
+                let end = end.ok_or_else(|| A::Error::missing_field("end"))?;
+                Ok(OwnedDateRange(start..end))
+            }
+        }
+
+        deserializer.deserialize_map(DateRangeVisitor)
+    }
+}
+
+/// A serialization wrapper for a single clause in an expression.
+struct ClauseRef<'a> {
+    op: Option<BooleanOp>,
+    term: &'a Term,
+}
+
+impl Serialize for ClauseRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(op) = &self.op {
+            map.serialize_entry("op", op)?;
+        }
+        match self.term {
+            Term::Field(field) => {
+                map.serialize_entry("field", &field.field_type())?;
+                map.serialize_entry("value", field.value())?;
+            }
+            Term::SubmittedDate(range) => {
+                map.serialize_entry("submitted_date", &DateRange(range))?;
+            }
+            Term::LastUpdatedDate(range) => {
+                map.serialize_entry("last_updated_date", &DateRange(range))?;
+            }
+            Term::Group(expr) => {
+                map.serialize_entry("group", expr.as_ref())?;
+            }
+        }
+        map.end()
+    }
+}
+
+/// A deserialization wrapper for a single clause in an expression.
+struct Clause {
+    op: Option<BooleanOp>,
+    term: Term,
+}
+
+impl<'de> Deserialize<'de> for Clause {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        const KEYS: &[&str] = &[
+            "op",
+            "field",
+            "value",
+            "submitted_date",
+            "last_updated_date",
+            "group",
+        ];
+
+        struct ClauseVisitor;
+
+        impl<'de> Visitor<'de> for ClauseVisitor {
+            type Value = Clause;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a search query clause")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                const EXACTLY_ONE: &str = "a clause must contain exactly one of `field`, \
+                    `submitted_date`, `last_updated_date`, or `group`";
+
+                let mut op = None;
+                let mut field_type = None;
+                let mut value: Option<String> = None;
+                let mut term = None;
+
+                while let Some(key) = map.next_key::<String>()? {
+                    let other = match key.as_str() {
+                        "op" => {
+                            op = Some(map.next_value()?);
+                            continue;
+                        }
+                        "field" => {
+                            field_type = Some(map.next_value()?);
+                            continue;
+                        }
+                        "value" => {
+                            value = Some(map.next_value()?);
+                            continue;
+                        }
+                        "submitted_date" => {
+                            Term::SubmittedDate(map.next_value::<OwnedDateRange>()?.0)
+                        }
+                        "last_updated_date" => {
+                            Term::LastUpdatedDate(map.next_value::<OwnedDateRange>()?.0)
+                        }
+                        "group" => Term::Group(Box::new(map.next_value()?)),
+                        other => return Err(A::Error::unknown_field(other, KEYS)),
+                    };
+                    if term.replace(other).is_some() {
+                        return Err(A::Error::custom(EXACTLY_ONE));
+                    }
+                }
+
+                let term = match (field_type, value, term) {
+                    (Some(field_type), Some(value), None) => {
+                        Term::Field(init_field(field_type, value)?)
+                    }
+                    (None, None, Some(term)) => term,
+                    (Some(_), None, None) => return Err(A::Error::missing_field("value")),
+                    (None, Some(_), None) => return Err(A::Error::missing_field("field")),
+                    _ => return Err(A::Error::custom(EXACTLY_ONE)),
+                };
+
+                Ok(Clause { op, term })
+            }
+        }
+
+        deserializer.deserialize_map(ClauseVisitor)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for Expr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.tail.len() + 1))?;
+        for (op, term) in self.terms() {
+            seq.serialize_element(&ClauseRef { op, term })?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for Expr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ExprVisitor;
+
+        impl<'de> Visitor<'de> for ExprVisitor {
+            type Value = Expr;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a non-empty list of search query clauses")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let Some(head) = seq.next_element::<Clause>()? else {
+                    return Err(A::Error::invalid_length(0, &self));
+                };
+                if head.op.is_some() {
+                    return Err(A::Error::custom(
+                        "the first clause in a search query cannot have an operator",
+                    ));
+                }
+
+                let mut expr = Expr::new(head.term);
+                while let Some(clause) = seq.next_element::<Clause>()? {
+                    let op = clause.op.ok_or_else(|| A::Error::missing_field("op"))?;
+                    expr.tail.push((op, clause.term));
+                }
+                Ok(expr)
+            }
+        }
+
+        deserializer.deserialize_seq(ExprVisitor)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for FieldGroup {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.expr() {
+            Some(expr) => expr.serialize(serializer),
+            None => Err(<S::Error as ser::Error>::custom(format!(
+                "field group `{self}` cannot be parsed"
+            ))),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for FieldGroup {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Expr::deserialize(deserializer).map(FieldGroup::from)
+    }
+}
+
+/// A (de)serialization wrapper for the pagination parameters.
+struct Pagination {
+    start: u16,
+    max_results: u16,
+}
+
+impl Serialize for Pagination {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("start", &self.start)?;
+        map.serialize_entry("max_results", &self.max_results)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Pagination {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PaginationVisitor;
+
+        impl<'de> Visitor<'de> for PaginationVisitor {
+            type Value = Pagination;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map with keys `start` and `max_results`")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut start = None;
+                let mut max_results = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "start" => start = Some(map.next_value()?),
+                        "max_results" => max_results = Some(map.next_value()?),
+                        other => {
+                            return Err(A::Error::unknown_field(other, &["start", "max_results"]));
+                        }
+                    }
+                }
+                Ok(Pagination {
+                    start: start.unwrap_or(0),
+                    max_results: max_results
+                        .ok_or_else(|| A::Error::missing_field("max_results"))?,
+                })
+            }
+        }
+
+        deserializer.deserialize_map(PaginationVisitor)
+    }
+}
+
+/// A (de)serialization wrapper for the sort parameters.
+struct Sort {
+    by: SortBy,
+    order: SortOrder,
+}
+
+impl Serialize for Sort {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("by", &self.by)?;
+        map.serialize_entry("order", &self.order)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Sort {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SortVisitor;
+
+        impl<'de> Visitor<'de> for SortVisitor {
+            type Value = Sort;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map with keys `by` and `order`")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut by = None;
+                let mut order = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "by" => by = Some(map.next_value()?),
+                        "order" => order = Some(map.next_value()?),
+                        other => return Err(A::Error::unknown_field(other, &["by", "order"])),
+                    }
+                }
+                Ok(Sort {
+                    by: by.ok_or_else(|| A::Error::missing_field("by"))?,
+                    order: order.unwrap_or_default(),
+                })
+            }
+        }
+
+        deserializer.deserialize_map(SortVisitor)
+    }
+}
+
+/// The keys in the serialized representation of a [`Query`].
+const QUERY_KEYS: &[&str] = &[
+    "search_query",
+    "id_list",
+    "pagination",
+    "sort",
+    "http",
+    "base_url",
+];
+
+/// Serialize a [`Query`] in a structured form.
+///
+/// ### Example
+/// ```
+/// use rsxiv::query::{Combine, Field, Query, SortBy, SortOrder};
+///
+/// let mut query = Query::new();
+/// query
+///     .sort(SortBy::SubmittedDate, SortOrder::Ascending)
+///     .search_query()
+///     .init(Field::ti("proton").unwrap())
+///     .and_not(Field::au("Bob").unwrap());
+///
+/// assert_eq!(
+///     serde_json::to_string(&query).unwrap(),
+///     concat!(
+///         r#"{"search_query":[{"field":"title","value":"proton"},"#,
+///         r#"{"op":"ANDNOT","field":"author","value":"Bob"}],"#,
+///         r#""sort":{"by":"submittedDate","order":"ascending"}}"#,
+///     )
+/// );
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for Query {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// Serialize the comma-separated identifier list as a sequence.
+        struct IdList<'a>(&'a str);
+
+        impl Serialize for IdList<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.0.split(','))
+            }
+        }
+
+        let mut state = serializer.serialize_struct("Query", QUERY_KEYS.len())?;
+
+        if self.search_query.is_empty() {
+            state.skip_field("search_query")?;
+        } else {
+            match self.search_expr() {
+                Some(expr) => state.serialize_field("search_query", &expr)?,
+                None => {
+                    return Err(<S::Error as ser::Error>::custom(format!(
+                        "search query `{}` cannot be parsed",
+                        self.search_query
+                    )));
+                }
+            }
+        }
+
+        if self.id_list.is_empty() {
+            state.skip_field("id_list")?;
+        } else {
+            state.serialize_field("id_list", &IdList(&self.id_list))?;
+        }
+
+        match self.pagination {
+            Some((start, max_results)) => {
+                state.serialize_field("pagination", &Pagination { start, max_results })?
+            }
+            None => state.skip_field("pagination")?,
+        }
+
+        match self.sort {
+            Some((by, order)) => state.serialize_field("sort", &Sort { by, order })?,
+            None => state.skip_field("sort")?,
+        }
+
+        if self.http {
+            state.serialize_field("http", &true)?;
+        } else {
+            state.skip_field("http")?;
+        }
+
+        match &self.base_url {
+            Some(base_url) => state.serialize_field("base_url", base_url.as_str())?,
+            None => state.skip_field("base_url")?,
+        }
+
+        state.end()
+    }
+}
+
+/// Deserialize a [`Query`] from the structured form produced by its [`Serialize`]
+/// implementation.
+///
+/// Every key is optional. Identifiers are validated and field values are checked using the same
+/// rules as [`Field::init`]. The pagination must satisfy the limits of [`Query::paginate`].
+///
+/// ### Example
+/// ```
+/// use rsxiv::query::Query;
+///
+/// let query: Query = serde_json::from_str(
+///     r#"{
+///         "search_query": [
+///             { "field": "cat", "value": "math.CA" },
+///             { "op": "AND", "group": [
+///                 { "field": "author", "value": "Bob" },
+///                 { "op": "OR", "field": "author", "value": "John" }
+///             ] }
+///         ],
+///         "pagination": { "start": 0, "max_results": 100 }
+///     }"#,
+/// ).unwrap();
+///
+/// assert_eq!(
+///     query.url().as_str(),
+///     "https://export.arxiv.org/api/query?search_query=cat%3Amath.CA+AND+%28au%3ABob+OR+au%3AJohn%29&start=0&max_results=100"
+/// );
+///
+/// // invalid field values are rejected
+/// assert!(serde_json::from_str::<Query>(
+///     r#"{ "search_query": [{ "field": "title", "value": "a OR (b" }] }"#
+/// ).is_err());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for Query {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct QueryVisitor;
+
+        impl<'de> Visitor<'de> for QueryVisitor {
+            type Value = Query;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an arXiv API query")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut query = Query::new();
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "search_query" => {
+                            let expr: Expr = map.next_value()?;
+                            query.search_query = expr.to_string();
+                        }
+                        "id_list" => {
+                            let ids: Vec<String> = map.next_value()?;
+                            let mut id_list = query.id_list();
+                            id_list.clear();
+                            for id in ids {
+                                let parsed = ArticleId::parse(&id).map_err(|_| {
+                                    A::Error::invalid_value(
+                                        Unexpected::Str(&id),
+                                        &"an arXiv identifier",
+                                    )
+                                })?;
+                                id_list.push(&parsed);
+                            }
+                        }
+                        "pagination" => {
+                            let Pagination { start, max_results } = map.next_value()?;
+                            if query.paginate(start, max_results).is_none() {
+                                return Err(A::Error::custom(
+                                    "pagination requires `start <= 30000` and `max_results <= 2000`",
+                                ));
+                            }
+                        }
+                        "sort" => {
+                            let Sort { by, order } = map.next_value()?;
+                            query.sort(by, order);
+                        }
+                        "http" => {
+                            query.http = map.next_value()?;
+                        }
+                        "base_url" => {
+                            let raw: String = map.next_value()?;
+                            let base_url = Url::parse(&raw).map_err(A::Error::custom)?;
+                            if query.base_url(base_url).is_none() {
+                                return Err(A::Error::invalid_value(
+                                    Unexpected::Str(&raw),
+                                    &"an http or https URL without a query or fragment",
+                                ));
+                            }
+                        }
+                        other => return Err(A::Error::unknown_field(other, QUERY_KEYS)),
+                    }
+                }
+
+                Ok(query)
+            }
+        }
+
+        deserializer.deserialize_struct("Query", QUERY_KEYS, QueryVisitor)
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::query::expr::DATE_FORMAT;
 use crate::query::field::{Combine, Field, FieldGroup};
 
 #[test]
@@ -42,3 +43,677 @@ fn test_url() {
         "https://export.arxiv.org/api/query?search_query=%28ti%3Aa+OR+rn%3Ab+ANDNOT+all%3Ac%29+AND+au%3Ab&start=20&max_results=10&sortBy=submittedDate&sortOrder=ascending"
     );
 }
+
+#[test]
+fn test_page_planner() {
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+
+    use crate::response::Pagination;
+
+    // a simulated corpus of submission dates, one every 7 minutes
+    let origin = NaiveDateTime::new(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), NaiveTime::MIN);
+    let corpus: Vec<NaiveDateTime> = (0..75000)
+        .map(|n| origin + TimeDelta::minutes(7 * n))
+        .collect();
+
+    // respond to the query by returning the indices of the corpus entries in the page
+    let respond = |query: &Query| -> (Pagination, Vec<usize>) {
+        let (start, max_results) = query.pagination.unwrap();
+        assert!(start <= MAX_START && max_results <= MAX_RESULTS);
+
+        let matching: Vec<usize> = match query.search_query.rsplit_once("submittedDate:[") {
+            Some((_, range)) => {
+                let (from, to) = range.trim_end_matches(']').split_once(" TO ").unwrap();
+                let from = NaiveDateTime::parse_from_str(from, DATE_FORMAT).unwrap();
+                let to = NaiveDateTime::parse_from_str(to, DATE_FORMAT).unwrap();
+                (0..corpus.len())
+                    .filter(|idx| from <= corpus[*idx] && corpus[*idx] <= to)
+                    .collect()
+            }
+            None => (0..corpus.len()).collect(),
+        };
+
+        let page = matching
+            .iter()
+            .skip(start as usize)
+            .take(max_results as usize)
+            .copied()
+            .collect();
+        let pagination = Pagination {
+            total_results: matching.len() as u64,
+            start_index: start as u64,
+            items_per_page: max_results as u64,
+        };
+        (pagination, page)
+    };
+
+    let mut query = Query::new();
+    query.search_query().init(Field::cat("math.CA").unwrap());
+
+    let last = *corpus.last().unwrap();
+    for narrow in [false, true] {
+        let mut planner = PagePlanner::new(query.clone(), corpus.len() as u64);
+        if narrow {
+            planner.date_range(origin..=last).unwrap();
+        }
+        let mut seen = vec![0; corpus.len()];
+        while let Some(page) = planner.next_query() {
+            let (pagination, indices) = respond(&page);
+            if planner.update(&pagination) {
+                for idx in indices {
+                    seen[idx] += 1;
+                }
+            }
+        }
+        assert!(seen.iter().all(|count| *count == 1));
+    }
+
+    // date ranges have minute precision
+    let mut planner = PagePlanner::new(Query::new(), 0);
+    assert!(
+        planner
+            .date_range(origin..=origin + TimeDelta::seconds(30))
+            .is_none()
+    );
+    assert!(planner.date_range(last..=origin).is_none());
+    assert!(planner.date_range(origin..=origin).is_some());
+
+    // a small query is paged without splitting
+    let mut planner = PagePlanner::new(Query::new(), 4500);
+    planner.page_size(1000).unwrap();
+    let starts: Vec<_> = std::iter::from_fn(|| planner.next_query())
+        .map(|query| query.pagination.unwrap())
+        .collect();
+    assert_eq!(
+        starts,
+        [
+            (0, 1000),
+            (1000, 1000),
+            (2000, 1000),
+            (3000, 1000),
+            (4000, 1000)
+        ]
+    );
+}
+
+#[test]
+fn test_id_list() {
+    use crate::id::{ArticleId, Validated};
+
+    let mut query = Query::new();
+    query
+        .id_list()
+        .push(&ArticleId::parse("2301.00001").unwrap())
+        .extend([
+            Validated::parse("math.CA/0309136v2").unwrap(),
+            Validated::parse("1204.0012").unwrap(),
+        ]);
+    assert_eq!(
+        query.url().to_string(),
+        "https://export.arxiv.org/api/query?id_list=2301.00001%2Cmath%2F0309136v2%2C1204.0012"
+    );
+}
+
+#[test]
+fn test_query_batcher() {
+    use std::num::NonZero;
+
+    use crate::id::ArticleId;
+
+    let ids: Vec<ArticleId> = (1..=5000)
+        .map(|n| {
+            ArticleId::new(
+                2015,
+                6,
+                None,
+                NonZero::new(n).unwrap(),
+                Some(NonZero::new(2).unwrap()),
+            )
+            .unwrap()
+        })
+        .collect();
+
+    let mut template = Query::new();
+    template.sort(SortBy::LastUpdatedDate, SortOrder::Ascending);
+
+    let batches: Vec<Query> = QueryBatcher::new(ids.iter().copied())
+        .template(template)
+        .max_url_len(8000)
+        .collect();
+
+    let mut returned = Vec::new();
+    for query in &batches {
+        let url = query.url();
+        assert!(url.as_str().len() <= 8000);
+        assert!(
+            url.as_str()
+                .ends_with("&sortBy=lastUpdatedDate&sortOrder=ascending")
+        );
+
+        let count = query.id_list.split(',').count();
+        assert_eq!(query.pagination, Some((0, count as u16)));
+        returned.extend(
+            query
+                .id_list
+                .split(',')
+                .map(|id| ArticleId::parse(id).unwrap()),
+        );
+    }
+    assert_eq!(returned, ids);
+    // the URL length is the binding constraint
+    assert!(batches.len() > 5000 / 2000 + 1);
+
+    // the identifier count is the binding constraint
+    let batches: Vec<Query> = QueryBatcher::new(ids.iter().copied())
+        .max_ids(1000)
+        .max_url_len(usize::MAX)
+        .collect();
+    assert_eq!(batches.len(), 5);
+    assert!(
+        batches
+            .iter()
+            .all(|query| query.pagination == Some((0, 1000)))
+    );
+
+    // a single identifier is always returned
+    let batches: Vec<Query> = QueryBatcher::new(ids.iter().take(3).copied())
+        .max_url_len(0)
+        .collect();
+    assert_eq!(batches.len(), 3);
+}
+
+#[test]
+fn test_form_body() {
+    assert_eq!(Query::new().form_body(), "");
+
+    let mut query = Query::new();
+    query
+        .http()
+        .sort(SortBy::Relevance, SortOrder::Descending)
+        .paginate(40, 20)
+        .unwrap()
+        .search_query()
+        .init(Field::ti("a b").unwrap())
+        .or(Field::au("c").unwrap());
+
+    let url = query.url();
+    assert_eq!(
+        query.endpoint().as_str(),
+        "http://export.arxiv.org/api/query"
+    );
+    assert_eq!(url.query(), Some(query.form_body().as_str()));
+    assert_eq!(
+        query.form_body(),
+        "search_query=ti%3Aa+b+OR+au%3Ac&start=40&max_results=20&sortBy=relevance&sortOrder=descending"
+    );
+}
+
+#[test]
+fn test_canonicalize() {
+    use crate::id::{ArticleId, Validated};
+
+    let mut query = Query::new();
+    query
+        .sort(SortBy::SubmittedDate, SortOrder::Descending)
+        .paginate(0, 10)
+        .unwrap()
+        .id_list()
+        .push(&Validated::parse("2301.00001v2").unwrap())
+        .push(&Validated::parse("hep-th.AB/0309013").unwrap())
+        .push(&ArticleId::parse("2301.00001").unwrap())
+        .push(&ArticleId::parse("hep-th/0309013").unwrap())
+        .push(&ArticleId::parse("2301.00001v2").unwrap());
+    query.canonicalize();
+
+    assert_eq!(query.pagination, None);
+    assert_eq!(query.id_list, "hep-th/0309013,2301.00001,2301.00001v2");
+    assert_eq!(
+        query.url().as_str(),
+        "https://export.arxiv.org/api/query?id_list=hep-th%2F0309013%2C2301.00001%2C2301.00001v2&sortBy=submittedDate&sortOrder=descending"
+    );
+
+    // canonicalization is idempotent
+    let key = query.cache_key();
+    query.canonicalize();
+    assert_eq!(query.cache_key(), key);
+
+    // non-default parameters are preserved
+    let mut query = Query::new();
+    query.paginate(0, 20).unwrap();
+    assert!(query.cache_key().ends_with("?start=0&max_results=20"));
+
+    // the hash is stable
+    assert_eq!(Query::new().cache_hash(), 0x4af0141f1ac64225);
+
+    // identifiers which cannot be parsed are kept, so distinct queries have distinct keys
+    let mut first = Query::new();
+    first.id_list = "2301.00002,invalid-b,2301.00001,invalid-a,invalid-b".to_owned();
+    let mut second = Query::new();
+    second.id_list = "2301.00002,2301.00001".to_owned();
+    assert_ne!(first.cache_key(), second.cache_key());
+    first.canonicalize();
+    assert_eq!(first.id_list, "2301.00001,2301.00002,invalid-a,invalid-b");
+}
+
+#[test]
+fn test_expr_roundtrip() {
+    let date = |y, m, d| {
+        chrono::NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    };
+
+    let group = FieldGroup::init(Field::au("Bob").unwrap())
+        .or(Field::au("John").unwrap())
+        .and(date(2020, 1, 1)..date(2021, 1, 1));
+
+    let mut query = Query::new();
+    query
+        .search_query()
+        .init(Field::ti("proton").unwrap())
+        .and_not(group)
+        .or(Field::rn("NASA-123").unwrap());
+
+    let expr = query.search_expr().unwrap();
+    assert_eq!(expr.to_string(), query.search_query);
+    assert_eq!(expr.terms().count(), 3);
+    assert!(matches!(&expr.tail[0].1, Term::Group(inner) if inner.tail.len() == 2));
+
+    assert!(Query::new().search_expr().is_none());
+    for invalid in [
+        "ti:a AND (au:b",
+        "xx:a",
+        "ti:a ANDOR au:b",
+        "submittedDate:[2020 TO 2021]",
+    ] {
+        assert!(Expr::parse(invalid).is_none(), "{invalid}");
+    }
+
+    // values which would merge with a neighbouring operator are rejected, so that every group
+    // can be parsed
+    for value in ["x AND", "x OR", "x ANDNOT", " AND", " ORx"] {
+        assert!(Field::ti(value).is_none(), "{value:?}");
+    }
+    for value in ["ANDx", "x ANDy", "OR x", "x ORDER"] {
+        let group = FieldGroup::init(Field::ti(value).unwrap()).or(Field::au("y").unwrap());
+        assert_eq!(group.expr().unwrap().tail.len(), 1, "{value:?}");
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_query_serde() {
+    let mut query = Query::new();
+    query
+        .paginate(10, 50)
+        .unwrap()
+        .sort(SortBy::LastUpdatedDate, SortOrder::Descending)
+        .search_query()
+        .init(Field::cat("math.CA").unwrap())
+        .and(FieldGroup::init(Field::au("Bob").unwrap()).or(Field::au("John").unwrap()));
+    query
+        .id_list()
+        .push(&ArticleId::parse("2301.00001v2").unwrap());
+
+    let json = serde_json::to_string(&query).unwrap();
+    let parsed: Query = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.url(), query.url());
+
+    let parsed: Query =
+        serde_json::from_str(r#"{ "id_list": ["math.CA/0309136"], "http": true }"#).unwrap();
+    assert_eq!(
+        parsed.url().as_str(),
+        "http://export.arxiv.org/api/query?id_list=math%2F0309136"
+    );
+
+    for invalid in [
+        r#"{ "id_list": ["not-an-id"] }"#,
+        r#"{ "pagination": { "start": 0, "max_results": 2001 } }"#,
+        r#"{ "search_query": [] }"#,
+        r#"{ "search_query": [{ "op": "AND", "field": "title", "value": "a" }] }"#,
+        r#"{ "search_query": [{ "field": "ti", "value": "a" }, { "field": "ti", "value": "b" }] }"#,
+        r#"{ "search_query": [{ "field": "title", "value": "a", "group": [] }] }"#,
+        r#"{ "search_query": [{ "field": "title", "value": "a:b" }] }"#,
+        r#"{ "sort": { "by": "date" } }"#,
+        r#"{ "unknown": 1 }"#,
+    ] {
+        assert!(serde_json::from_str::<Query>(invalid).is_err(), "{invalid}");
+    }
+
+    // a search query which cannot be parsed is an error rather than being skipped
+    let mut query = Query::new();
+    query.search_query = "ti:x AND OR au:y".to_owned();
+    assert!(serde_json::to_string(&query).is_err());
+
+    let group: FieldGroup = serde_json::from_str(
+        r#"[{ "field": "au", "value": "Bob" }, { "op": "OR", "submitted_date": { "start": "2020-01-01T00:00:00", "end": "2021-01-01T00:00:00" } }]"#,
+    )
+    .unwrap();
+    assert_eq!(
+        group.expr().unwrap().to_string(),
+        "au:Bob OR submittedDate:[202001010000 TO 202101010000]"
+    );
+}
+
+#[test]
+fn test_base_url() {
+    let mut query = Query::new();
+    query
+        .base_url(Url::parse("https://127.0.0.1:8000/mirror/").unwrap())
+        .unwrap()
+        .paginate(0, 5)
+        .unwrap();
+    assert_eq!(
+        query.url().as_str(),
+        "https://127.0.0.1:8000/mirror/api/query?start=0&max_results=5"
+    );
+    assert_eq!(
+        query.http().endpoint().as_str(),
+        "http://127.0.0.1:8000/mirror/api/query"
+    );
+    assert_eq!(
+        query.default_base_url().endpoint().as_str(),
+        "http://export.arxiv.org/api/query"
+    );
+
+    for invalid in [
+        "mailto:a@b.c",
+        "data:text/plain,abc",
+        "http://localhost/#top",
+    ] {
+        assert!(query.base_url(Url::parse(invalid).unwrap()).is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    {
+        let mut query = Query::new();
+        query
+            .base_url(Url::parse("http://localhost:8080").unwrap())
+            .unwrap();
+        let json = serde_json::to_string(&query).unwrap();
+        assert_eq!(json, r#"{"base_url":"http://localhost:8080/"}"#);
+        let parsed: Query = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.url(), query.url());
+
+        assert!(serde_json::from_str::<Query>(r#"{"base_url":"ftp://localhost"}"#).is_err());
+    }
+}
+
+#[test]
+fn test_from_author() {
+    use crate::response::AuthorName;
+
+    let field = |name: &str, mode| {
+        Field::from_author(&AuthorName::from_arxiv(name), mode).map(|f| f.to_string())
+    };
+
+    // combining diacritical marks are removed
+    assert_eq!(
+        field("Jose\u{301} Garci\u{301}a", AuthorMatch::Exact).as_deref(),
+        Some("au:garcia_jose")
+    );
+    assert_eq!(
+        field("Ursula von der Leyen", AuthorMatch::SurnameInitial).as_deref(),
+        Some("au:von_der_leyen_u")
+    );
+    assert_eq!(
+        field("Jean-Pierre Serre", AuthorMatch::SurnameInitial).as_deref(),
+        Some("au:serre_j")
+    );
+    assert_eq!(
+        field("Hans-Jürgen Schröder", AuthorMatch::Exact).as_deref(),
+        Some("au:schroder_hans-jurgen")
+    );
+    assert_eq!(
+        field("Martin Luther King Jr.", AuthorMatch::Surname).as_deref(),
+        Some("au:king")
+    );
+    assert_eq!(
+        field("Martin Luther King Jr.", AuthorMatch::Exact).as_deref(),
+        Some("au:king_martin_luther_jr")
+    );
+    // a single name has no first initial
+    assert_eq!(
+        field("Plato", AuthorMatch::SurnameInitial).as_deref(),
+        Some("au:plato")
+    );
+    // no ASCII approximation
+    assert_eq!(field("山田", AuthorMatch::Exact), None);
+}
+
+#[test]
+fn test_category() {
+    let categories: Vec<Category> = Category::all().collect();
+    assert!(categories.windows(2).all(|w| w[0].as_str() < w[1].as_str()));
+    for category in &categories {
+        assert_eq!(Category::from_id(category.as_str()), Some(*category));
+    }
+
+    assert!(Field::cat("cs.LG").is_some());
+    assert!(Field::cat("cs.lg").is_none());
+    assert!(Field::cat("cs.*").is_none());
+
+    let group = FieldGroup::cat("q-bio*").unwrap();
+    assert_eq!(group.expr().unwrap().terms().count(), 11);
+    assert_eq!(
+        group.to_string(),
+        FieldGroup::cat("q-bio.*").unwrap().to_string()
+    );
+    assert!(FieldGroup::cat("*").is_none());
+    assert!(FieldGroup::cat("q-bi*").is_none());
+
+    #[cfg(feature = "serde")]
+    assert!(
+        serde_json::from_str::<Field<String>>(r#"{ "field": "cat", "value": "math.Ca" }"#).is_err()
+    );
+}
+
+#[test]
+fn test_lint() {
+    use crate::id::ArticleId;
+
+    let date = |y, m, d| {
+        chrono::NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    };
+
+    let mut query = Query::new();
+    query
+        .search_query()
+        .init(Field::au("Bob").unwrap())
+        .and(date(2021, 1, 1)..date(2020, 1, 1));
+    assert_eq!(
+        query.lint(),
+        [Lint::InvertedDateRange(date(2021, 1, 1)..date(2020, 1, 1))]
+    );
+    assert_eq!(query.lint()[0].severity(), Severity::Error);
+
+    let mut query = Query::new();
+    query
+        .search_query()
+        .init(date(2020, 1, 1)..date(2021, 1, 1))
+        .and_not(FieldGroup::init(Field::au("Bob").unwrap()).or(Field::au("John").unwrap()));
+    assert_eq!(query.lint(), [Lint::OnlyExclusions]);
+
+    let mut query = Query::new();
+    query
+        .paginate(100, 100)
+        .unwrap()
+        .search_query()
+        .init(Field::ti("proton").unwrap());
+    assert_eq!(query.lint(), [Lint::UnstablePaging]);
+    query.sort(SortBy::SubmittedDate, SortOrder::Ascending);
+    assert!(query.lint().is_empty());
+
+    // an excluded range covering the submission month
+    let mut query = Query::new();
+    query
+        .search_query()
+        .init(Field::ti("proton").unwrap())
+        .and_not(date(2022, 12, 1)..date(2023, 2, 1));
+    query.id_list().extend([
+        ArticleId::parse("2301.00001").unwrap(),
+        ArticleId::parse("2302.00001").unwrap(),
+    ]);
+    assert_eq!(
+        query.lint(),
+        [Lint::ExcludedId(ArticleId::parse("2301.00001").unwrap())]
+    );
+
+    // date ranges are not required when combined with `OR`
+    query
+        .search_query()
+        .extend()
+        .unwrap()
+        .or(date(2000, 1, 1)..date(2001, 1, 1));
+    assert!(query.lint().is_empty());
+}
+
+#[test]
+fn test_presets() {
+    use crate::{id::ArticleId, response::AuthorName};
+
+    let since = chrono::NaiveDate::from_ymd_opt(2024, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+
+    assert!(Query::new_submissions([], since).is_none());
+    assert!(Query::watchlist_updates([], since).is_none());
+    assert!(Query::category_updates([], since).is_none());
+    assert!(
+        Query::authors_in_categories(
+            &[AuthorName::from_arxiv("A. Author")],
+            AuthorMatch::Surname,
+            []
+        )
+        .is_none()
+    );
+    assert!(
+        Query::authors_in_categories(
+            &[],
+            AuthorMatch::Surname,
+            [Category::from_id("hep-th").unwrap()]
+        )
+        .is_none()
+    );
+
+    let query =
+        Query::watchlist_updates([ArticleId::parse("2301.00001v3").unwrap()], since).unwrap();
+    let expr = query.search_expr().unwrap();
+    assert!(matches!(&expr.head, Term::LastUpdatedDate(range) if range.start == since));
+    assert_eq!(Expr::parse(&expr.to_string()).unwrap(), expr);
+    assert!(query.lint().is_empty());
+
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_string(&query).unwrap();
+        assert!(json.contains(r#""last_updated_date":{"start":"2024-01-01T00:00:00""#));
+        let parsed: Query = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.url(), query.url());
+    }
+}
+
+#[test]
+fn test_matches() {
+    use crate::response::parse;
+
+    let xml = include_bytes!("../response/tests/query.xml");
+    let response = parse(xml).unwrap();
+    let matching = |query: &Query| -> Vec<String> {
+        response
+            .entries
+            .iter()
+            .filter(|entry| query.matches(entry))
+            .map(|entry| entry.id.to_string())
+            .collect()
+    };
+    let search = |field: Field<&str>| {
+        let mut query = Query::new();
+        query.search_query().init(field);
+        query
+    };
+
+    // every entry matches the query which produced the response
+    let mut query = Query::new();
+    query
+        .search_query()
+        .init(Field::all("electron").unwrap())
+        .and(Field::all("proton").unwrap());
+    assert_eq!(matching(&query).len(), 10);
+    assert!(matching(&Query::new()).is_empty());
+
+    // tokenisation ignores case, punctuation and diacritics, and supports wildcards
+    assert_eq!(
+        matching(&search(Field::ti("VEPP").unwrap())),
+        ["nucl-ex/0408020v1"]
+    );
+    assert_eq!(
+        matching(&search(Field::ti("\"positrons on the proton\"").unwrap())),
+        ["nucl-ex/0408020v1"]
+    );
+    assert_eq!(matching(&search(Field::ti("wakefield").unwrap())).len(), 1);
+    assert_eq!(
+        matching(&search(Field::ti("wake*").unwrap())),
+        matching(&search(Field::ti("wakefield").unwrap()))
+    );
+    assert!(matching(&search(Field::ti("wake").unwrap())).is_empty());
+    assert_eq!(
+        matching(&search(Field::au("Meißner").unwrap())),
+        ["1205.6628v2"]
+    );
+    assert_eq!(
+        matching(&search(Field::au("Meissner_U").unwrap())),
+        ["1205.6628v2"]
+    );
+    assert!(matching(&search(Field::au("Meissner_H").unwrap())).is_empty());
+
+    // comment, journal reference, category and report number fields
+    assert_eq!(
+        matching(&search(Field::co("ECLOUD'12").unwrap())),
+        ["1309.4668v1"]
+    );
+    assert_eq!(
+        matching(&search(Field::jr("\"Phys. Rev. D\"").unwrap())),
+        ["2102.00018v2"]
+    );
+    assert_eq!(
+        matching(&search(Field::cat("physics.plasm-ph").unwrap())),
+        ["1610.08734v3", "1602.03411v1"]
+    );
+    assert!(matching(&search(Field::rn("CERN-2013-002").unwrap())).is_empty());
+
+    // date ranges are inclusive at minute granularity
+    let published = chrono::NaiveDate::from_ymd_opt(1999, 10, 7)
+        .unwrap()
+        .and_hms_opt(12, 46, 0)
+        .unwrap();
+    let mut query = Query::new();
+    query.search_query().init(published..published);
+    assert_eq!(matching(&query), ["nucl-th/9910021v3"]);
+    query.search_query().init(LastUpdated(published..published));
+    assert!(matching(&query).is_empty());
+
+    // operators are applied from left to right
+    let mut query = Query::new();
+    query
+        .search_query()
+        .init(Field::cat("hep-ex").unwrap())
+        .or(Field::cat("nucl-th").unwrap())
+        .and_not(Field::au("faber").unwrap());
+    assert_eq!(
+        matching(&query),
+        ["1606.02159v1", "0803.1617v1", "1205.6628v2", "1401.3666v2"]
+    );
+
+    // identifier lists restrict the results, ignoring versions if none is specified
+    let mut query = search(Field::all("proton").unwrap());
+    query
+        .id_list()
+        .push(&ArticleId::parse("1602.03411").unwrap())
+        .push(&ArticleId::parse("1401.3666v1").unwrap());
+    assert_eq!(matching(&query), ["1602.03411v1"]);
+}
@@ -21,23 +21,47 @@
 //! assert_eq!(response.entries[0].id, ArticleId::parse("2206.06921v3").unwrap());
 //! ```
 //!
+//! To process large responses one entry at a time, or to skip fields which are not needed, use an
+//! [`EntryIter`]. Responses which are not in memory, such as compressed files, can be read from an
+//! [`io::BufRead`](std::io::BufRead) source with [`parse_reader`] or an [`EntryReader`].
+//!
+//! By default, a single invalid entry causes the entire response to fail to parse. To keep the
+//! valid entries and report the invalid entries separately, use [`parse_recover`].
+//!
+//! ## Writing
+//! The [`write()`] function converts a [`Response`] back into the XML format of the arXiv API, for
+//! instance to generate test fixtures or filtered feeds. The output is read back by [`parse`] into
+//! the same response.
+//!
 //! ## Deserialization
 //! The [`Response`] struct also exposes a [`serde`] deserialization interface. View the
 //! [deserialization docs](crate::de) for more detail.
 //!
 //! [api]: https://info.arxiv.org/help/api/user-manual.html
 
+mod iter;
+mod recover;
+mod stream;
 #[cfg(test)]
 mod tests;
+mod write;
 mod xml;
 
 use std::{borrow::Cow, fmt::Display};
 
 use chrono::{DateTime, FixedOffset};
 
-pub(crate) use self::xml::ResponseReader;
+#[cfg(feature = "mock-server")]
+pub(crate) use self::write::{write_error, write_feed};
 #[cfg(feature = "serde")]
-pub(crate) use self::xml::Term;
+pub(crate) use self::xml::{RawAuthor, Term};
+pub(crate) use self::xml::{RawEntry, ResponseReader};
+pub use self::{
+    iter::{EntryIter, Fields},
+    recover::{EntryError, parse_recover},
+    stream::{EntryReader, parse_reader},
+    write::write,
+};
 use crate::id::ArticleId;
 
 /// A parsed arXiv API response.
@@ -61,6 +85,20 @@ pub struct Response<T> {
     pub entries: T,
 }
 
+impl Response<Vec<Entry<'_>>> {
+    /// Convert into a response whose entries do not borrow from the response buffer.
+    ///
+    /// See [`Entry::into_owned`] for more detail.
+    #[must_use]
+    pub fn into_owned(self) -> Response<Vec<OwnedEntry>> {
+        Response {
+            updated: self.updated,
+            pagination: self.pagination,
+            entries: self.entries.into_iter().map(Entry::into_owned).collect(),
+        }
+    }
+}
+
 /// Pagination information for paged queries.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Pagination {
@@ -75,57 +113,19 @@ pub struct Pagination {
 /// Parse a [`Response<Vec<Entry<'r>>>`] from the raw XML response returned by the arXiv API.
 ///
 /// This implementation borrows as much as possible from the input data, but sometimes borrowing is
-/// impossible due to the presence of XML escape sequences.
+/// impossible due to the presence of XML escape sequences. To read the entries one at a time, or
+/// to skip unused fields, use an [`EntryIter`] instead. To keep the valid entries when some
+/// entries are invalid, use [`parse_recover`].
 pub fn parse<'r>(xml: &'r [u8]) -> Result<Response<Vec<Entry<'r>>>, ResponseError> {
-    let (updated, pagination, mut reader) = ResponseReader::init(xml)?;
+    let iter = EntryIter::new(xml)?;
+    let updated = iter.updated();
+    let pagination = iter.pagination().clone();
 
     // if this does not fit in a `usize`, `xml` should not fit in memory
     let expected_count = pagination.items_per_page.min(pagination.total_results) as usize;
     let mut entries = Vec::with_capacity(expected_count);
-
-    while let Some(id) = reader.next_id()? {
-        let id = ArticleId::parse_bytes(id)?;
-
-        let title = reader.next_title()?;
-
-        let updated = DateTime::parse_from_rfc3339(&reader.next_updated()?)?;
-
-        let summary = reader.next_summary()?;
-
-        let mut categories = Vec::new();
-        while let Some(term) = reader.next_category()? {
-            categories.push(term.get()?.into_owned().into())
-        }
-
-        let published = DateTime::parse_from_rfc3339(&reader.next_published()?)?;
-
-        let comment = reader.next_comment()?;
-
-        let primary_category = reader.next_primary_category()?.get()?.into_owned().into();
-
-        let journal_ref = reader.next_journal_ref()?;
-
-        let mut authors = Vec::new();
-        while reader.next_author()? {
-            let name = AuthorName::from_arxiv(&reader.next_author_name()?);
-            let affiliation = reader.next_author_affiliation()?;
-            authors.push(Author { name, affiliation });
-        }
-        let doi = reader.next_doi()?;
-
-        entries.push(Entry {
-            id,
-            updated,
-            published,
-            title,
-            summary,
-            authors,
-            doi,
-            comment,
-            journal_ref,
-            primary_category,
-            categories,
-        });
+    for entry in iter {
+        entries.push(entry?);
     }
 
     Ok(Response {
@@ -375,6 +375,107 @@ pub struct Entry<'r> {
     pub primary_category: Cow<'r, str>,
     /// The arXiv or ACM or MSC category for an article.
     pub categories: Vec<Cow<'r, str>>,
+    /// Links to the abstract page, the PDF and the resolved DOI of the article.
+    pub links: Links<'r>,
+}
+
+/// The links in an entry of the arXiv API response.
+///
+/// Links are identified by their `title` and `rel` attributes in the format documented in the
+/// [arXiv API manual][api], and otherwise by their URL, since the arXiv API does not always
+/// include the attributes.
+///
+/// [api]: https://info.arxiv.org/help/api/user-manual.html#52-details-of-atom-results-returned
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(all(test, feature = "serde"), derive(serde::Deserialize))]
+pub struct Links<'r> {
+    /// The URL of the abstract page, like `https://arxiv.org/abs/2301.00001v1`.
+    pub abs: Option<Cow<'r, str>>,
+    /// The URL of the PDF, like `https://arxiv.org/pdf/2301.00001v1`.
+    pub pdf: Option<Cow<'r, str>>,
+    /// The URL of the resolved DOI, like `https://doi.org/10.1000/182`.
+    pub doi: Option<Cow<'r, str>>,
+}
+
+impl<'r> Links<'r> {
+    /// Record a `<link>` tag with the provided attributes, ignoring unrecognized links and links
+    /// which were already recorded.
+    pub(crate) fn push(&mut self, href: Cow<'r, str>, rel: Option<&str>, title: Option<&str>) {
+        let slot = match (title, rel) {
+            (Some("pdf"), _) => &mut self.pdf,
+            (Some("doi"), _) => &mut self.doi,
+            (_, Some("alternate")) => &mut self.abs,
+            _ if href.contains("/pdf/") => &mut self.pdf,
+            _ if href.contains("/abs/") => &mut self.abs,
+            _ if href.contains("doi.org/") => &mut self.doi,
+            _ => return,
+        };
+        if slot.is_none() {
+            *slot = Some(href);
+        }
+    }
+
+    /// Convert into links which do not borrow from the response.
+    #[must_use]
+    pub fn into_owned(self) -> Links<'static> {
+        let own = |link: Option<Cow<'_, str>>| link.map(|link| Cow::Owned(link.into_owned()));
+        Links {
+            abs: own(self.abs),
+            pdf: own(self.pdf),
+            doi: own(self.doi),
+        }
+    }
+}
+
+/// An [`Entry`] which does not borrow from the response, obtained with [`Entry::into_owned`].
+///
+/// Owned entries can outlive the response buffer, and can be sent across threads or stored in
+/// long-lived caches.
+pub type OwnedEntry = Entry<'static>;
+
+impl Entry<'_> {
+    /// Convert into an entry which does not borrow from the response.
+    ///
+    /// Every borrowed field is copied into a [`Cow::Owned`], in the same way that a `Cow` field is
+    /// deserialized by `serde` unless it is explicitly borrowed.
+    ///
+    /// ### Example
+    /// ```
+    /// use rsxiv::response::{OwnedEntry, parse};
+    ///
+    /// // abridged arXiv response
+    /// let xml = // r#"<feed xmlns...
+    #[doc = include_str!("response/tests/query_doc.txt")]
+    /// let entries: Vec<OwnedEntry> = {
+    ///     let buffer = xml.as_bytes().to_vec();
+    ///     let response = parse(&buffer).unwrap();
+    ///     response.entries.into_iter().map(|entry| entry.into_owned()).collect()
+    /// };
+    /// assert_eq!(entries[0].title, "Attainable forms of Assouad spectra");
+    /// ```
+    #[must_use]
+    pub fn into_owned(self) -> OwnedEntry {
+        Entry {
+            id: self.id,
+            updated: self.updated,
+            published: self.published,
+            title: Cow::Owned(self.title.into_owned()),
+            summary: Cow::Owned(self.summary.into_owned()),
+            authors: self.authors.into_iter().map(Author::into_owned).collect(),
+            doi: self.doi.map(|doi| Cow::Owned(doi.into_owned())),
+            comment: self.comment.map(|comment| Cow::Owned(comment.into_owned())),
+            journal_ref: self
+                .journal_ref
+                .map(|journal_ref| Cow::Owned(journal_ref.into_owned())),
+            primary_category: Cow::Owned(self.primary_category.into_owned()),
+            categories: self
+                .categories
+                .into_iter()
+                .map(|category| Cow::Owned(category.into_owned()))
+                .collect(),
+            links: self.links.into_owned(),
+        }
+    }
 }
 
 /// An error which results from response parsing.
@@ -402,6 +503,112 @@ pub enum ResponseError {
     InvalidId(crate::id::IdError),
     /// A custom error which occurs during deserialization.
     Custom(String),
+    /// An error with information about where in the response it occurred.
+    Context {
+        /// Where the error occurred.
+        context: Box<ErrorContext>,
+        /// The underlying error.
+        error: Box<ResponseError>,
+    },
+}
+
+impl ResponseError {
+    /// Returns the information about where the error occurred, if any.
+    #[must_use]
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::Context { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying error, without the information about where it occurred.
+    ///
+    /// ### Example
+    /// ```
+    /// use rsxiv::response::{ResponseError, parse};
+    ///
+    /// // abridged arXiv response, without the `title` tag
+    /// let xml = // r#"<feed xmlns...
+    #[doc = include_str!("response/tests/query_doc.txt")]
+    /// let xml = xml.replace("<title>Attainable forms of Assouad spectra</title>", "");
+    /// let err = parse(xml.as_bytes()).unwrap_err();
+    ///
+    /// assert!(matches!(err.without_context(), ResponseError::MissingTag("title")));
+    /// let context = err.context().unwrap();
+    /// assert_eq!(context.index, Some(0));
+    /// assert_eq!(context.id.unwrap().to_string(), "2206.06921v3");
+    /// assert_eq!(context.field.as_deref(), Some("title"));
+    /// assert!(xml[context.position.unwrap()..].starts_with("<entry>"));
+    /// ```
+    #[must_use]
+    pub fn without_context(&self) -> &Self {
+        match self {
+            Self::Context { error, .. } => error,
+            error => error,
+        }
+    }
+
+    /// Returns the context of the error, adding an empty context if there is none.
+    fn context_mut(&mut self) -> &mut ErrorContext {
+        if !matches!(self, Self::Context { .. }) {
+            // the placeholder is immediately overwritten
+            let error = std::mem::replace(self, Self::TrailingEntries);
+            *self = Self::Context {
+                context: Box::default(),
+                error: Box::new(error),
+            };
+        }
+        match self {
+            Self::Context { context, .. } => context,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Prepend a segment to the path of the field in which the error occurred. A segment of the
+    /// form `[n]` is an index into a sequence.
+    pub(crate) fn in_field(mut self, segment: impl Display) -> Self {
+        let context = self.context_mut();
+        let mut field = segment.to_string();
+        if let Some(inner) = context.field.take() {
+            if !inner.starts_with('[') {
+                field.push('.');
+            }
+            field.push_str(&inner);
+        }
+        context.field = Some(field);
+        self
+    }
+
+    /// Set the entry in which the error occurred, unless it is already set.
+    pub(crate) fn in_entry(mut self, index: usize, id: Option<ArticleId>, position: usize) -> Self {
+        let context = self.context_mut();
+        context.index.get_or_insert(index);
+        if context.id.is_none() {
+            context.id = id;
+        }
+        context.position.get_or_insert(position);
+        self
+    }
+}
+
+/// Information about where a [`ResponseError`] occurred, returned by [`ResponseError::context`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// The 0-based index of the `<entry>` in the response, counting every entry.
+    pub index: Option<usize>,
+    /// The identifier of the entry, if it was read before the error occurred.
+    pub id: Option<ArticleId>,
+    /// The path to the field in which the error occurred, such as `title` or `authors[1].name`.
+    ///
+    /// When deserializing, this is the path in the deserialization format described in the
+    /// [`de` module](crate::de).
+    pub field: Option<String>,
+    /// The byte offset in the response at which the error occurred.
+    ///
+    /// This is the position of the error for malformed XML, and otherwise the start of the
+    /// `<entry>` tag.
+    pub position: Option<usize>,
 }
 
 /// An article author.
@@ -414,9 +621,25 @@ pub struct Author<'r> {
     pub affiliation: Option<Cow<'r, str>>,
 }
 
+/// An [`Author`] which does not borrow from the response, obtained with [`Author::into_owned`].
+pub type OwnedAuthor = Author<'static>;
+
+impl Author<'_> {
+    /// Convert into an author which does not borrow from the response.
+    #[must_use]
+    pub fn into_owned(self) -> OwnedAuthor {
+        Author {
+            name: self.name,
+            affiliation: self
+                .affiliation
+                .map(|affiliation| Cow::Owned(affiliation.into_owned())),
+        }
+    }
+}
+
 /// Various trait implementations for [`ResponseError`]
 mod error_impl {
-    use super::ResponseError;
+    use super::{ErrorContext, ResponseError};
 
     impl From<crate::id::IdError> for ResponseError {
         fn from(value: crate::id::IdError) -> Self {
@@ -476,9 +699,43 @@ mod error_impl {
                     write!(f, "Entry contains invalid identifier: {id_error}")
                 }
                 ResponseError::TrailingEntries => write!(f, "Response contains trailing entries"),
+                ResponseError::Context { context, error } => write!(f, "{context}: {error}"),
+            }
+        }
+    }
+
+    impl std::fmt::Display for ErrorContext {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let mut sep = "";
+            if let Some(index) = self.index {
+                write!(f, "entry {index}")?;
+                sep = ", ";
+            }
+            if let Some(id) = &self.id {
+                write!(f, "{sep}id `{id}`")?;
+                sep = ", ";
+            }
+            if let Some(field) = &self.field {
+                write!(f, "{sep}field `{field}`")?;
+                sep = ", ";
             }
+            if let Some(position) = self.position {
+                write!(f, "{sep}byte {position}")?;
+            }
+            Ok(())
         }
     }
 
-    impl std::error::Error for ResponseError {}
+    impl std::error::Error for ResponseError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                ResponseError::Parse(error) => Some(error),
+                ResponseError::Attribute(attr_error) => Some(attr_error),
+                ResponseError::InvalidDateTime(parse_error) => Some(parse_error),
+                ResponseError::InvalidId(id_error) => Some(id_error),
+                ResponseError::Context { error, .. } => error.source(),
+                _ => None,
+            }
+        }
+    }
 }
@@ -0,0 +1,329 @@
+//! # Lazy entry iteration
+//!
+//! An iterator which parses the entries of an arXiv API response one at a time.
+use std::{fmt, ops::BitOr};
+
+use chrono::{DateTime, FixedOffset};
+
+use super::{
+    Author, AuthorName, Entry, Links, Pagination, RawEntry, ResponseError, ResponseReader,
+};
+use crate::id::ArticleId;
+
+/// A set of [`Entry`] fields to be read by an [`EntryIter`].
+///
+/// Combine fields with `|`. The identifier of an entry is always read.
+///
+/// ### Example
+/// ```
+/// use rsxiv::response::Fields;
+///
+/// let fields = Fields::TITLE | Fields::AUTHORS;
+/// assert!(fields.contains(Fields::TITLE));
+/// assert!(!fields.contains(Fields::SUMMARY));
+/// assert!(Fields::ALL.contains(fields));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fields(u16);
+
+impl Fields {
+    /// No fields other than the identifier.
+    pub const NONE: Self = Self(0);
+    /// The [`Entry::title`] field.
+    pub const TITLE: Self = Self(1 << 0);
+    /// The [`Entry::updated`] field.
+    pub const UPDATED: Self = Self(1 << 1);
+    /// The [`Entry::summary`] field.
+    pub const SUMMARY: Self = Self(1 << 2);
+    /// The [`Entry::categories`] field.
+    pub const CATEGORIES: Self = Self(1 << 3);
+    /// The [`Entry::published`] field.
+    pub const PUBLISHED: Self = Self(1 << 4);
+    /// The [`Entry::comment`] field.
+    pub const COMMENT: Self = Self(1 << 5);
+    /// The [`Entry::primary_category`] field.
+    pub const PRIMARY_CATEGORY: Self = Self(1 << 6);
+    /// The [`Entry::journal_ref`] field.
+    pub const JOURNAL_REF: Self = Self(1 << 7);
+    /// The [`Entry::authors`] field.
+    pub const AUTHORS: Self = Self(1 << 8);
+    /// The [`Entry::doi`] field.
+    pub const DOI: Self = Self(1 << 9);
+    /// The [`Entry::links`] field.
+    pub const LINKS: Self = Self(1 << 10);
+    /// Every field.
+    pub const ALL: Self = Self((1 << 11) - 1);
+
+    /// Returns if every field in `other` is also in `self`.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the fields in `self` which are not in `other`.
+    #[must_use]
+    pub const fn without(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+}
+
+impl Default for Fields {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl BitOr for Fields {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl fmt::Debug for Fields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const NAMES: [(Fields, &str); 11] = [
+            (Fields::TITLE, "TITLE"),
+            (Fields::UPDATED, "UPDATED"),
+            (Fields::SUMMARY, "SUMMARY"),
+            (Fields::CATEGORIES, "CATEGORIES"),
+            (Fields::PUBLISHED, "PUBLISHED"),
+            (Fields::COMMENT, "COMMENT"),
+            (Fields::PRIMARY_CATEGORY, "PRIMARY_CATEGORY"),
+            (Fields::JOURNAL_REF, "JOURNAL_REF"),
+            (Fields::AUTHORS, "AUTHORS"),
+            (Fields::DOI, "DOI"),
+            (Fields::LINKS, "LINKS"),
+        ];
+        f.write_str("Fields(")?;
+        let mut names = NAMES
+            .iter()
+            .filter(|(field, _)| self.contains(*field))
+            .map(|(_, name)| *name);
+        match names.next() {
+            Some(name) => f.write_str(name)?,
+            None => f.write_str("NONE")?,
+        }
+        for name in names {
+            write!(f, " | {name}")?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// A lazy iterator over the entries in an arXiv API response.
+///
+/// The response header is parsed by [`EntryIter::new`], and each entry is parsed when it is
+/// requested. Iteration stops after the first error.
+///
+/// By default, every field is read. Use [`EntryIter::fields`] to only read some of the fields;
+/// the XML elements of the remaining fields are skipped without reading their contents, and the
+/// fields are set to their default values: empty strings and lists, `None`, and the Unix epoch
+/// for dates.
+///
+/// ### Example
+/// ```
+/// use rsxiv::response::{EntryIter, Fields};
+///
+/// // abridged arXiv response
+/// let xml = // r#"<feed xmlns...
+#[doc = include_str!("tests/query_doc.txt")]
+/// let mut entries = EntryIter::new(xml.as_bytes()).unwrap();
+/// assert_eq!(entries.pagination().total_results, 1);
+///
+/// entries.fields(Fields::TITLE | Fields::CATEGORIES);
+/// let entry = entries.next().unwrap().unwrap();
+/// assert_eq!(entry.title, "Attainable forms of Assouad spectra");
+/// assert_eq!(entry.categories, ["math.CA", "math.DS", "math.MG"]);
+/// // the summary was skipped
+/// assert!(entry.summary.is_empty());
+///
+/// assert!(entries.next().is_none());
+/// ```
+pub struct EntryIter<'r> {
+    reader: ResponseReader<'r>,
+    updated: DateTime<FixedOffset>,
+    pagination: Pagination,
+    fields: Fields,
+    finished: bool,
+}
+
+impl<'r> EntryIter<'r> {
+    /// Parse the header of the raw XML response returned by the arXiv API, and prepare to read the
+    /// entries.
+    pub fn new(xml: &'r [u8]) -> Result<Self, ResponseError> {
+        let (updated, pagination, reader) = ResponseReader::init(xml)?;
+        Ok(Self {
+            reader,
+            updated,
+            pagination,
+            fields: Fields::ALL,
+            finished: false,
+        })
+    }
+
+    /// Set the fields to read from the remaining entries.
+    pub fn fields(&mut self, fields: Fields) -> &mut Self {
+        self.reader.fields(fields);
+        self.fields = fields;
+        self
+    }
+
+    /// When the query was last updated.
+    #[must_use]
+    pub fn updated(&self) -> DateTime<FixedOffset> {
+        self.updated
+    }
+
+    /// The pagination information of the response.
+    #[must_use]
+    pub fn pagination(&self) -> &Pagination {
+        &self.pagination
+    }
+}
+
+/// Returns the contents of a required tag, or a [`ResponseError::MissingTag`] error.
+fn require<T>(value: Option<T>, tag: &'static str) -> Result<T, ResponseError> {
+    value.ok_or(ResponseError::MissingTag(tag))
+}
+
+/// Read a field, attaching the name of the field to any error.
+fn field<T>(
+    name: &'static str,
+    read: impl FnOnce() -> Result<T, ResponseError>,
+) -> Result<T, ResponseError> {
+    read().map_err(|err| err.in_field(name))
+}
+
+/// Convert the contents of the entry with the provided identifier, skipping the fields which are
+/// not in `fields`.
+pub(super) fn read_entry<'r>(
+    id: &'r [u8],
+    raw: RawEntry<'r>,
+    fields: Fields,
+) -> Result<Entry<'r>, ResponseError> {
+    let id = field("id", || Ok(ArticleId::parse_bytes(id)?))?;
+
+    let links = if fields.contains(Fields::LINKS) {
+        field("links", || raw.links())?
+    } else {
+        Links::default()
+    };
+
+    let title = if fields.contains(Fields::TITLE) {
+        field("title", || require(raw.title, "title"))?
+    } else {
+        Default::default()
+    };
+
+    let updated = if fields.contains(Fields::UPDATED) {
+        field("updated", || {
+            Ok(DateTime::parse_from_rfc3339(&require(
+                raw.updated,
+                "updated",
+            )?)?)
+        })?
+    } else {
+        Default::default()
+    };
+
+    let summary = if fields.contains(Fields::SUMMARY) {
+        field("summary", || require(raw.summary, "summary"))?
+    } else {
+        Default::default()
+    };
+
+    let mut categories = Vec::new();
+    if fields.contains(Fields::CATEGORIES) {
+        for (idx, term) in raw.categories.iter().enumerate() {
+            let category = term
+                .get()
+                .map_err(|err| err.in_field(format_args!("[{idx}]")).in_field("categories"))?;
+            categories.push(category.into_owned().into())
+        }
+    }
+
+    let published = if fields.contains(Fields::PUBLISHED) {
+        field("published", || {
+            Ok(DateTime::parse_from_rfc3339(&require(
+                raw.published,
+                "published",
+            )?)?)
+        })?
+    } else {
+        Default::default()
+    };
+
+    let comment = raw.comment.filter(|_| fields.contains(Fields::COMMENT));
+
+    let primary_category = if fields.contains(Fields::PRIMARY_CATEGORY) {
+        field("primary_category", || {
+            let term = require(raw.primary_category.as_ref(), "primary_category")?;
+            Ok(term.get()?.into_owned().into())
+        })?
+    } else {
+        Default::default()
+    };
+
+    let journal_ref = raw
+        .journal_ref
+        .filter(|_| fields.contains(Fields::JOURNAL_REF));
+
+    let mut authors = Vec::new();
+    if fields.contains(Fields::AUTHORS) {
+        for (idx, author) in raw.authors.into_iter().enumerate() {
+            let name = require(author.name, "name").map_err(|err| {
+                err.in_field("name")
+                    .in_field(format_args!("[{idx}]"))
+                    .in_field("authors")
+            })?;
+            let name = AuthorName::from_arxiv(&name);
+            let affiliation = author.affiliation;
+            authors.push(Author { name, affiliation });
+        }
+    }
+
+    let doi = raw.doi.filter(|_| fields.contains(Fields::DOI));
+
+    Ok(Entry {
+        id,
+        updated,
+        published,
+        title,
+        summary,
+        authors,
+        doi,
+        comment,
+        journal_ref,
+        primary_category,
+        categories,
+        links,
+    })
+}
+
+impl<'r> Iterator for EntryIter<'r> {
+    type Item = Result<Entry<'r>, ResponseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let entry = match self.reader.next_entry() {
+            Ok(Some((id, raw))) => read_entry(id, raw, self.fields)
+                .map_err(|err| self.reader.entry_error(err, Some(id))),
+            Ok(None) => {
+                self.finished = true;
+                return None;
+            }
+            Err(err) => Err(err),
+        };
+        if entry.is_err() {
+            self.finished = true;
+        }
+        Some(entry)
+    }
+}
+
+impl std::iter::FusedIterator for EntryIter<'_> {}
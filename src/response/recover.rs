@@ -0,0 +1,85 @@
+//! # Error recovery
+//!
+//! Response parsing which skips over invalid entries rather than failing the entire response.
+use std::ops::Range;
+
+use super::{Entry, Fields, Response, ResponseError, ResponseReader, iter::read_entry};
+
+/// An entry which could not be parsed by [`parse_recover`].
+#[derive(Debug)]
+pub struct EntryError {
+    /// The 0-based index of the `<entry>` in the response, counting every entry.
+    pub index: usize,
+    /// The byte range of the entry in the response, including the opening and closing tags.
+    pub span: Range<usize>,
+    /// The reason that the entry could not be parsed.
+    pub error: ResponseError,
+}
+
+/// Parse a [`Response<Vec<Entry<'r>>>`] from the raw XML response returned by the arXiv API,
+/// recovering from errors in individual entries.
+///
+/// This is the same as [`parse`](super::parse), except that an entry which cannot be parsed, for
+/// instance because of an invalid identifier, a missing tag, or a malformed date, is omitted from
+/// the response and reported as an [`EntryError`]. Unlike [`parse`](super::parse), an entry
+/// without an `<id>` tag is also reported, with a [`ResponseError::MissingTag`] error.
+///
+/// An error is still returned if the response header is invalid, if the XML is malformed, or if
+/// the response is an arXiv API error.
+///
+/// ### Example
+/// ```
+/// use rsxiv::response::{ResponseError, parse_recover};
+///
+/// // abridged arXiv response, without the `primary_category` tag
+/// let xml = // r#"<feed xmlns...
+#[doc = include_str!("tests/query_doc.txt")]
+/// let xml = xml.replace(r#"<arxiv:primary_category term="math.CA"/>"#, "");
+/// let (response, errors) = parse_recover(xml.as_bytes()).unwrap();
+///
+/// assert!(response.entries.is_empty());
+/// assert_eq!(errors[0].index, 0);
+/// assert!(xml[errors[0].span.clone()].starts_with("<entry>"));
+/// assert!(matches!(
+///     errors[0].error.without_context(),
+///     ResponseError::MissingTag("primary_category")
+/// ));
+/// ```
+pub fn parse_recover<'r>(
+    xml: &'r [u8],
+) -> Result<(Response<Vec<Entry<'r>>>, Vec<EntryError>), ResponseError> {
+    let (updated, pagination, mut reader) = ResponseReader::init(xml)?;
+
+    // if this does not fit in a `usize`, `xml` should not fit in memory
+    let expected_count = pagination.items_per_page.min(pagination.total_results) as usize;
+    let mut entries = Vec::with_capacity(expected_count);
+    let mut errors = Vec::new();
+
+    let mut index = 0;
+    while let Some(raw) = reader.next_raw_entry()? {
+        let span = raw.span.clone();
+        let entry = match raw.id() {
+            Ok(Some(id)) => {
+                read_entry(id, raw, Fields::ALL).map_err(|err| reader.entry_error(err, Some(id)))
+            }
+            Ok(None) => {
+                Err(reader.entry_error(ResponseError::MissingTag("id").in_field("id"), None))
+            }
+            Err(err) => Err(reader.entry_error(err.in_field("id"), None)),
+        };
+        match entry {
+            Ok(entry) => entries.push(entry),
+            Err(error) => errors.push(EntryError { index, span, error }),
+        }
+        index += 1;
+    }
+
+    Ok((
+        Response {
+            updated,
+            pagination,
+            entries,
+        },
+        errors,
+    ))
+}
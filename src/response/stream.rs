@@ -0,0 +1,270 @@
+//! # Buffered entry reading
+//!
+//! An iterator which reads the entries of an arXiv API response from an [`io::BufRead`] source.
+use std::io;
+
+use chrono::{DateTime, FixedOffset};
+use quick_xml::{
+    Reader, Writer,
+    events::{BytesStart, Event},
+};
+
+use super::{
+    Fields, OwnedEntry, Pagination, Response, ResponseError, ResponseReader,
+    iter::read_entry,
+    xml::{is_missing_end_tag, missing_end_tag},
+};
+use crate::id::ArticleId;
+
+/// Returns if the event opens an `<entry>`.
+fn is_entry_start(event: &Event<'_>) -> bool {
+    matches!(event, Event::Start(start) if start.name().as_ref() == b"entry")
+}
+
+/// An iterator over the entries in an arXiv API response which is read from an [`io::BufRead`]
+/// source, such as a file or a decompressing reader.
+///
+/// This is the counterpart of [`EntryIter`](super::EntryIter) for responses which are not in
+/// memory. Only one entry is held in memory at a time, and each entry is returned as an owned
+/// [`OwnedEntry`]. Iteration stops after the first error, including any I/O error from the
+/// source, which is returned as a [`ResponseError::Parse`].
+///
+/// Wrap an [`io::Read`] source in an [`io::BufReader`] to read from it.
+///
+/// ### Example
+/// ```
+/// use std::io::BufReader;
+///
+/// use rsxiv::response::{EntryReader, Fields};
+///
+/// // abridged arXiv response
+/// let xml = // r#"<feed xmlns...
+#[doc = include_str!("tests/query_doc.txt")]
+/// let source = BufReader::new(xml.as_bytes());
+/// let mut entries = EntryReader::new(source).unwrap();
+/// assert_eq!(entries.pagination().total_results, 1);
+///
+/// entries.fields(Fields::TITLE);
+/// let entry = entries.next().unwrap().unwrap();
+/// assert_eq!(entry.title, "Attainable forms of Assouad spectra");
+/// assert!(entries.next().is_none());
+/// ```
+pub struct EntryReader<R> {
+    reader: Reader<R>,
+    /// The buffer for the events returned by the reader.
+    buf: Vec<u8>,
+    /// The raw XML of the current entry.
+    entry: Vec<u8>,
+    updated: DateTime<FixedOffset>,
+    pagination: Pagination,
+    fields: Fields,
+    /// The index of the most recent entry, if any.
+    index: Option<usize>,
+    /// The byte offset of the most recent entry in the source.
+    entry_start: usize,
+    /// Whether the opening tag of the next entry was already read.
+    pending: bool,
+    finished: bool,
+}
+
+impl<R: io::BufRead> EntryReader<R> {
+    /// Read the header of the arXiv API response from the source, and prepare to read the
+    /// entries.
+    pub fn new(source: R) -> Result<Self, ResponseError> {
+        let mut reader = Reader::from_reader(source);
+        let mut buf = Vec::new();
+
+        // the header is small, so it is buffered and read with the usual header parser
+        let mut header = Vec::new();
+        let mut writer = Writer::new(&mut header);
+        let mut entry_start;
+        let pending = loop {
+            buf.clear();
+            entry_start = reader.buffer_position() as usize;
+            let event = reader.read_event_into(&mut buf)?;
+            if is_entry_start(&event) {
+                break true;
+            } else if let Event::Eof = event {
+                break false;
+            }
+            writer
+                .write_event(event)
+                .expect("writing to a `Vec` cannot fail");
+        };
+        let (updated, pagination, _) = ResponseReader::init(&header)?;
+
+        Ok(Self {
+            reader,
+            buf,
+            entry: Vec::new(),
+            updated,
+            pagination,
+            fields: Fields::ALL,
+            index: None,
+            entry_start,
+            pending,
+            finished: !pending,
+        })
+    }
+
+    /// Set the fields to read from the remaining entries.
+    ///
+    /// See [`EntryIter::fields`](super::EntryIter::fields) for more detail.
+    pub fn fields(&mut self, fields: Fields) -> &mut Self {
+        self.fields = fields;
+        self
+    }
+
+    /// When the query was last updated.
+    #[must_use]
+    pub fn updated(&self) -> DateTime<FixedOffset> {
+        self.updated
+    }
+
+    /// The pagination information of the response.
+    #[must_use]
+    pub fn pagination(&self) -> &Pagination {
+        &self.pagination
+    }
+
+    /// Copy the raw XML of the next entry into the entry buffer, returning `false` if there are no
+    /// more entries.
+    fn buffer_entry(&mut self) -> Result<bool, ResponseError> {
+        if !self.pending {
+            loop {
+                self.buf.clear();
+                self.entry_start = self.reader.buffer_position() as usize;
+                let event = self.reader.read_event_into(&mut self.buf)?;
+                if is_entry_start(&event) {
+                    break;
+                } else if let Event::Eof = event {
+                    return Ok(false);
+                }
+            }
+        }
+        self.pending = false;
+        self.index = Some(self.index.map_or(0, |index| index + 1));
+
+        self.entry.clear();
+        let mut writer = Writer::new(&mut self.entry);
+        writer
+            .write_event(Event::Start(BytesStart::new("entry")))
+            .expect("writing to a `Vec` cannot fail");
+        loop {
+            self.buf.clear();
+            let event = self.reader.read_event_into(&mut self.buf)?;
+            let end = match &event {
+                Event::End(end) => end.name().as_ref() == b"entry",
+                Event::Eof => return Err(missing_end_tag("entry")),
+                _ => false,
+            };
+            writer
+                .write_event(event)
+                .expect("writing to a `Vec` cannot fail");
+            if end {
+                break;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Attach the location of the most recent entry in the source, and the identifier of the
+    /// entry if it is known, to an error.
+    fn entry_error(&self, error: ResponseError, id: Option<&[u8]>) -> ResponseError {
+        let position = match error.without_context() {
+            ResponseError::Arxiv(_) | ResponseError::InvalidError(_) => return error,
+            err if is_missing_end_tag(err) => self.reader.buffer_position() as usize,
+            ResponseError::Parse(_) => self.reader.error_position() as usize,
+            _ => self.entry_start,
+        };
+        let Some(index) = self.index else {
+            return error;
+        };
+        let id = id.and_then(|id| ArticleId::parse_bytes(id).ok());
+        error.in_entry(index, id, position)
+    }
+
+    /// Read the next entry, skipping entries without an identifier.
+    fn read_next(&mut self) -> Result<Option<OwnedEntry>, ResponseError> {
+        while self
+            .buffer_entry()
+            .map_err(|err| self.entry_error(err, None))?
+        {
+            // the buffered entry is read without context, since the location of the buffered
+            // entry is not the location in the source
+            let mut reader = ResponseReader::new(&self.entry)?;
+            reader.fields(self.fields);
+            let Some(raw) = reader
+                .read_raw_entry()
+                .map_err(|err| self.entry_error(err, None))?
+            else {
+                continue;
+            };
+            match raw.id() {
+                Ok(Some(id)) => {
+                    return read_entry(id, raw, self.fields)
+                        .map(|entry| Some(entry.into_owned()))
+                        .map_err(|err| self.entry_error(err, Some(id)));
+                }
+                Ok(None) => {}
+                Err(err) => return Err(self.entry_error(err.in_field("id"), None)),
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<R: io::BufRead> Iterator for EntryReader<R> {
+    type Item = Result<OwnedEntry, ResponseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let entry = self.read_next();
+        if !matches!(entry, Ok(Some(_))) {
+            self.finished = true;
+        }
+        entry.transpose()
+    }
+}
+
+impl<R: io::BufRead> std::iter::FusedIterator for EntryReader<R> {}
+
+/// Parse a [`Response<Vec<OwnedEntry>>`] from an [`io::BufRead`] source containing the XML
+/// response returned by the arXiv API.
+///
+/// This is equivalent to [`parse`](super::parse), but reads the response incrementally rather than
+/// requiring the entire response in memory. To process the entries one at a time, use an
+/// [`EntryReader`] instead.
+///
+/// ### Example
+/// ```
+/// use std::io::BufReader;
+///
+/// use rsxiv::response::{parse, parse_reader};
+///
+/// // abridged arXiv response
+/// let xml = // r#"<feed xmlns...
+#[doc = include_str!("tests/query_doc.txt")]
+/// let response = parse_reader(BufReader::new(xml.as_bytes())).unwrap();
+/// assert_eq!(response, parse(xml.as_bytes()).unwrap());
+/// ```
+pub fn parse_reader<R: io::BufRead>(source: R) -> Result<Response<Vec<OwnedEntry>>, ResponseError> {
+    let reader = EntryReader::new(source)?;
+    let updated = reader.updated();
+    let pagination = reader.pagination().clone();
+
+    let expected_count = pagination.items_per_page.min(pagination.total_results) as usize;
+    let mut entries = Vec::with_capacity(expected_count);
+    for entry in reader {
+        entries.push(entry?);
+    }
+
+    Ok(Response {
+        updated,
+        pagination,
+        entries,
+    })
+}
@@ -23,3 +23,501 @@ fn test_arxiv_name_parse() {
     assert_name_eq("Jr", "", "Jr", "");
     assert_name_eq("", "", "", "");
 }
+
+#[test]
+fn test_write_roundtrip() {
+    let write_str = |response: &Response<Vec<Entry<'_>>>| {
+        let mut buffer = Vec::new();
+        write(&mut buffer, response).unwrap();
+        String::from_utf8(buffer).unwrap()
+    };
+
+    let response = parse(include_bytes!("tests/query.xml")).unwrap();
+    assert_eq!(
+        response.entries[1].authors[0].affiliation.as_deref(),
+        Some("Rutherford")
+    );
+    let xml = write_str(&response);
+    assert_eq!(parse(xml.as_bytes()).unwrap(), response);
+
+    let empty = parse(include_bytes!("tests/query_empty.xml")).unwrap();
+    assert_eq!(parse(write_str(&empty).as_bytes()).unwrap(), empty);
+
+    // text is escaped only where required, and datetimes keep their offset and precision
+    let mut response = response;
+    response.entries.truncate(1);
+    let entry = &mut response.entries[0];
+    entry.title = "0 &lt; x < 1 & y &amp; z".into();
+    entry.comment = Some("&#x27;quoted&#39;".into());
+    entry.categories.push("a\"b<c".into());
+    entry.doi = None;
+    entry.updated = DateTime::parse_from_rfc3339("2025-01-30T01:23:42.5+02:00").unwrap();
+    let xml = write_str(&response);
+    assert!(xml.contains("<title>0 &lt; x &lt; 1 &amp; y &amp; z</title>"));
+    assert!(xml.contains("<updated>2025-01-30T01:23:42.500+02:00</updated>"));
+    assert!(!xml.contains("arxiv:doi"));
+    let parsed = parse(xml.as_bytes()).unwrap();
+    assert_eq!(parsed.entries[0].title, "0 &lt; x &lt; 1 &amp; y &amp; z");
+    assert_eq!(parsed.entries[0].comment, response.entries[0].comment);
+    assert_eq!(parsed.entries[0].categories, response.entries[0].categories);
+    assert_eq!(parsed.entries[0].updated, response.entries[0].updated);
+}
+
+#[test]
+fn test_entry_iter() {
+    let xml = include_bytes!("tests/query.xml");
+    let response = parse(xml).unwrap();
+
+    // every field is read by default
+    let entries = EntryIter::new(xml).unwrap();
+    assert_eq!(entries.updated(), response.updated);
+    assert_eq!(entries.pagination(), &response.pagination);
+    assert_eq!(
+        entries.collect::<Result<Vec<_>, _>>().unwrap(),
+        response.entries
+    );
+
+    // only the identifier is read
+    let mut entries = EntryIter::new(xml).unwrap();
+    entries.fields(Fields::NONE);
+    let ids: Vec<_> = entries.map(|entry| entry.unwrap().id).collect();
+    assert_eq!(
+        ids,
+        response
+            .entries
+            .iter()
+            .map(|entry| entry.id)
+            .collect::<Vec<_>>()
+    );
+
+    // fields can be changed between entries, and skipped fields are left empty
+    let mut entries = EntryIter::new(xml).unwrap();
+    entries.fields(Fields::COMMENT | Fields::AUTHORS | Fields::DOI);
+    let first = entries.next().unwrap().unwrap();
+    assert_eq!(first.comment, response.entries[0].comment);
+    assert_eq!(first.authors, response.entries[0].authors);
+    assert!(first.title.is_empty() && first.summary.is_empty());
+    assert!(first.categories.is_empty() && first.primary_category.is_empty());
+    assert_eq!(first.updated, DateTime::<FixedOffset>::default());
+
+    entries.fields(Fields::ALL.without(Fields::SUMMARY | Fields::AUTHORS));
+    let second = entries.next().unwrap().unwrap();
+    assert_eq!(second.categories, response.entries[1].categories);
+    assert_eq!(second.doi, response.entries[1].doi);
+    assert!(second.summary.is_empty() && second.authors.is_empty());
+    assert_eq!(entries.count(), 8);
+
+    // iteration stops after an error
+    let xml =
+        include_str!("tests/query.xml").replace("nucl-ex/0408020v1</id>", "nucl-ex/04080201</id>");
+    let mut entries = EntryIter::new(xml.as_bytes()).unwrap();
+    let err = entries.next().unwrap().unwrap_err();
+    assert!(matches!(err.without_context(), ResponseError::InvalidId(_)));
+    assert!(entries.next().is_none());
+
+    // skipped elements are not read, so invalid text in a skipped field is not an error
+    let mut xml = include_bytes!("tests/query.xml").to_vec();
+    let summary = xml.windows(9).position(|tag| tag == b"<summary>").unwrap();
+    xml[summary + 9] = 0xff;
+    assert!(EntryIter::new(&xml).unwrap().next().unwrap().is_err());
+    let mut entries = EntryIter::new(&xml).unwrap();
+    entries.fields(Fields::ALL.without(Fields::SUMMARY));
+    assert_eq!(entries.filter(Result::is_ok).count(), 10);
+
+    // the message of an arXiv API error is read even if the summary is skipped
+    let xml = include_str!("tests/query.xml").replace(
+        "http://arxiv.org/abs/nucl-ex/0408020v1",
+        "http://arxiv.org/api/errors#incorrect_id_format",
+    );
+    let mut entries = EntryIter::new(xml.as_bytes()).unwrap();
+    entries.fields(Fields::TITLE);
+    assert!(matches!(
+        entries.next().unwrap(),
+        Err(ResponseError::Arxiv(msg)) if msg == response.entries[0].summary
+    ));
+
+    assert_eq!(
+        format!("{:?}", Fields::TITLE | Fields::DOI),
+        "Fields(TITLE | DOI)"
+    );
+    assert_eq!(format!("{:?}", Fields::NONE), "Fields(NONE)");
+}
+
+#[test]
+fn test_entry_reader() {
+    use std::io::{self, BufReader, Read};
+
+    let xml = include_bytes!("tests/query.xml");
+    let response = parse(xml).unwrap();
+
+    // a small buffer splits events across reads
+    let source = BufReader::with_capacity(7, &xml[..]);
+    assert_eq!(parse_reader(source).unwrap(), response);
+
+    let empty = include_bytes!("tests/query_empty.xml");
+    assert_eq!(parse_reader(&empty[..]).unwrap(), parse(empty).unwrap());
+
+    let mut entries = EntryReader::new(&xml[..]).unwrap();
+    entries.fields(Fields::TITLE);
+    let first = entries.next().unwrap().unwrap();
+    assert_eq!(first.title, response.entries[0].title);
+    assert!(first.authors.is_empty());
+    entries.fields(Fields::ALL);
+    assert_eq!(entries.next().unwrap().unwrap(), response.entries[1]);
+
+    // I/O errors stop the iteration
+    struct Truncated<'a>(&'a [u8]);
+    impl Read for Truncated<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                return Err(io::Error::other("connection reset"));
+            }
+            self.0.read(buf)
+        }
+    }
+    let mut entries = EntryReader::new(BufReader::new(Truncated(&xml[..3000]))).unwrap();
+    assert!(entries.next().unwrap().is_ok());
+    let err = entries.next().unwrap().unwrap_err();
+    assert!(matches!(
+        err.without_context(),
+        ResponseError::Parse(quick_xml::Error::Io(_))
+    ));
+    assert_eq!(err.context().unwrap().index, Some(1));
+    assert!(entries.next().is_none());
+}
+
+#[test]
+fn test_into_owned() {
+    let xml = include_str!("tests/query.xml").to_owned();
+    let response = parse(xml.as_bytes()).unwrap();
+    let expected = parse(include_bytes!("tests/query.xml")).unwrap();
+
+    let owned: Response<Vec<OwnedEntry>> = response.into_owned();
+    drop(xml);
+    assert_eq!(owned, expected);
+    assert!(
+        owned
+            .entries
+            .iter()
+            .all(|entry| matches!(entry.title, Cow::Owned(_)))
+    );
+
+    // owned entries can be sent to another thread
+    let authors: Vec<OwnedAuthor> = std::thread::spawn(move || {
+        owned
+            .entries
+            .into_iter()
+            .flat_map(|entry| entry.authors)
+            .map(Author::into_owned)
+            .collect()
+    })
+    .join()
+    .unwrap();
+    assert_eq!(authors.len(), 35);
+}
+
+/// The XML in the `query_doc.txt` fixture used by the doc tests.
+fn query_doc_xml() -> String {
+    include_str!("tests/query_doc.txt")
+        .lines()
+        .map(|line| line.trim_start_matches("# ").trim_start_matches("r#\""))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .replace("</feed>\"#;", "</feed>")
+}
+
+#[test]
+fn test_links() {
+    let response = parse(include_bytes!("tests/query.xml")).unwrap();
+    let links = &response.entries[3].links;
+    assert_eq!(
+        links.abs.as_deref(),
+        Some("https://arxiv.org/abs/1610.08734v3")
+    );
+    assert_eq!(
+        links.pdf.as_deref(),
+        Some("https://arxiv.org/pdf/1610.08734v3")
+    );
+    assert_eq!(
+        links.doi.as_deref(),
+        Some("https://doi.org/10.1103/PhysRevAccelBeams.20.101301")
+    );
+    assert_eq!(response.entries[0].links.doi, None);
+
+    // links are borrowed from the input unless they contain escape sequences
+    assert!(matches!(links.abs, Some(Cow::Borrowed(_))));
+    let xml = query_doc_xml().replace(
+        r#"<link href="https://arxiv.org/pdf/2206.06921v3"/>"#,
+        r#"<link href="https://arxiv.org/pdf/2206.06921v3?a=1&amp;b=2"></link>"#,
+    );
+    let entry = &parse(xml.as_bytes()).unwrap().entries[0];
+    assert!(matches!(
+        &entry.links.pdf,
+        Some(Cow::Owned(pdf)) if pdf == "https://arxiv.org/pdf/2206.06921v3?a=1&b=2"
+    ));
+
+    // the link attributes take precedence over the URL, and links may appear anywhere
+    let xml = query_doc_xml()
+        .replace(
+            r#"<link href="https://arxiv.org/abs/2206.06921v3"/>"#,
+            r#"<link href="http://arxiv.org/abs/2206.06921v3" rel="alternate" type="text/html"/>"#,
+        )
+        .replace(
+            r#"<link href="https://arxiv.org/pdf/2206.06921v3"/>"#,
+            "",
+        )
+        .replace(
+            r#"<link rel="related" href="https://doi.org/10.1512/iumj.2024.73.9928"/>"#,
+            r#"<link title="doi" href="http://dx.doi.org/10.1512/iumj.2024.73.9928" rel="related"/>
+    <link title="pdf" href="http://arxiv.org/pdf/2206.06921v3.pdf" rel="related" type="application/pdf"/>"#,
+        );
+    let entry = &parse(xml.as_bytes()).unwrap().entries[0];
+    assert_eq!(
+        entry.links,
+        Links {
+            abs: Some("http://arxiv.org/abs/2206.06921v3".into()),
+            pdf: Some("http://arxiv.org/pdf/2206.06921v3.pdf".into()),
+            doi: Some("http://dx.doi.org/10.1512/iumj.2024.73.9928".into()),
+        }
+    );
+
+    // skipping the links does not skip any other field
+    let mut entries = EntryIter::new(xml.as_bytes()).unwrap();
+    entries.fields(Fields::ALL.without(Fields::LINKS));
+    let skipped = entries.next().unwrap().unwrap();
+    assert_eq!(skipped.links, Links::default());
+    assert_eq!(
+        Entry {
+            links: Links::default(),
+            ..entry.clone()
+        },
+        skipped
+    );
+}
+
+#[test]
+fn test_entry_order() {
+    let xml = query_doc_xml();
+    let expected = parse(xml.as_bytes()).unwrap();
+
+    // the same entry with the child elements in a different order, and an unknown element
+    let start = xml.find("<entry>").unwrap();
+    let end = xml.find("</entry>").unwrap() + "</entry>".len();
+    let reordered = r#"<entry>
+    <id>http://arxiv.org/abs/2206.06921v3</id>
+    <updated>2025-01-30T01:23:42Z</updated>
+    <published>2022-06-14T15:35:46Z</published>
+    <title>Attainable forms of Assouad spectra</title>
+    <summary>Let $d\in\mathbb{N}$ and let $\varphi\colon(0,1)\to[0,d]$. We prove that there exists a set $F\subset\mathbb{R}^d$ such that $\operatorname{dim}_A^θF=\varphi(θ)$ for all $θ\in(0,1)$ if and only if for every $0&lt;λ&lt;θ&lt;1$, \[0\leq (1-λ)\varphi(λ)-(1-θ)\varphi(θ)\leq (θ-λ)\varphi\Bigl(\fracλθ\Bigr).\] In particular, the following behaviours which have not previously been witnessed in any examples are possible: the Assouad spectrum can be non-monotonic on every open set, and can fail to be Hölder in a neighbourhood of 1.</summary>
+    <author>
+      <name>Alex Rutar</name>
+    </author>
+    <arxiv:doi>10.1512/iumj.2024.73.9928</arxiv:doi>
+    <link rel="related" href="https://doi.org/10.1512/iumj.2024.73.9928"/>
+    <link href="https://arxiv.org/abs/2206.06921v3"/>
+    <link href="https://arxiv.org/pdf/2206.06921v3"/>
+    <arxiv:unknown><title>Not the title</title></arxiv:unknown>
+    <arxiv:comment>25 pages, 5 figures. v3: Many typo fixes and substantially improved exposition, especially in non-monotonic construction. Some numbering changes from v2. Results unchanged</arxiv:comment>
+    <arxiv:journal_ref>Indiana Univ. Math. J. 73 (2024), 1331-1356</arxiv:journal_ref>
+    <arxiv:primary_category term="math.CA"/>
+    <category term="math.CA" scheme="http://arxiv.org/schemas/atom"/>
+    <category term="math.DS" scheme="http://arxiv.org/schemas/atom"/>
+    <category term="math.MG" scheme="http://arxiv.org/schemas/atom"/>
+  </entry>"#;
+    let reordered = format!("{}{reordered}{}", &xml[..start], &xml[end..]);
+
+    let response = parse(reordered.as_bytes()).unwrap();
+    assert_eq!(response, expected);
+
+    // text is still borrowed from the input
+    assert!(matches!(response.entries[0].title, Cow::Borrowed(_)));
+
+    // tags with attributes may have an explicit closing tag
+    let explicit = reordered
+        .replace(
+            r#"<arxiv:primary_category term="math.CA"/>"#,
+            r#"<arxiv:primary_category term="math.CA"></arxiv:primary_category>"#,
+        )
+        .replace(
+            r#"<category term="math.DS" scheme="http://arxiv.org/schemas/atom"/>"#,
+            r#"<category term="math.DS" scheme="http://arxiv.org/schemas/atom"></category>"#,
+        )
+        .replace(
+            r#"<link href="https://arxiv.org/pdf/2206.06921v3"/>"#,
+            r#"<link href="https://arxiv.org/pdf/2206.06921v3"></link>"#,
+        );
+    assert_eq!(parse(explicit.as_bytes()).unwrap(), expected);
+}
+
+#[test]
+fn test_truncated() {
+    use std::io::BufReader;
+
+    let missing_end_tag = |err: &ResponseError, name: &str| {
+        matches!(
+            err.without_context(),
+            ResponseError::Parse(quick_xml::Error::IllFormed(
+                quick_xml::errors::IllFormedError::MissingEndTag(tag)
+            )) if tag == name
+        )
+    };
+
+    // the input ends inside an entry
+    let xml = query_doc_xml();
+    let truncated = &xml[..xml.find("</entry>").unwrap()];
+    let err = parse(truncated.as_bytes()).unwrap_err();
+    assert!(missing_end_tag(&err, "entry"), "{err:?}");
+    assert_eq!(err.context().unwrap().position, Some(truncated.len()));
+
+    let err = EntryReader::new(BufReader::new(truncated.as_bytes()))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap_err();
+    assert!(missing_end_tag(&err, "entry"), "{err:?}");
+    assert_eq!(err.context().unwrap().index, Some(0));
+
+    // the input ends inside an author
+    let truncated = &xml[..xml.find("</author>").unwrap()];
+    let err = parse(truncated.as_bytes()).unwrap_err();
+    assert!(missing_end_tag(&err, "author"), "{err:?}");
+}
+
+#[test]
+fn test_parse_recover() {
+    let xml = include_str!("tests/query.xml");
+    let expected = parse(xml.as_bytes()).unwrap();
+
+    // a response without errors
+    let (response, errors) = parse_recover(xml.as_bytes()).unwrap();
+    assert_eq!(response, expected);
+    assert!(errors.is_empty());
+
+    let xml = xml
+        // missing identifier
+        .replace("<id>http://arxiv.org/abs/1309.4668v1</id>", "")
+        // invalid identifier
+        .replace(
+            "http://arxiv.org/abs/1606.02159v1",
+            "http://arxiv.org/abs/1606.invalid",
+        )
+        // malformed date
+        .replace(
+            "<published>2021-01-29T19:00:16Z</published>",
+            "<published>29 January 2021</published>",
+        )
+        // identifier in an unexpected format
+        .replace(
+            "http://arxiv.org/abs/1401.3666v2",
+            "https://example.com/1401.3666v2",
+        );
+    assert!(parse(xml.as_bytes()).is_err());
+
+    let (response, errors) = parse_recover(xml.as_bytes()).unwrap();
+    assert_eq!(response.updated, expected.updated);
+    assert_eq!(response.pagination, expected.pagination);
+    assert_eq!(
+        response.entries,
+        [0, 3, 5, 6, 7, 8].map(|idx| expected.entries[idx].clone())
+    );
+
+    assert_eq!(
+        errors.iter().map(|e| e.index).collect::<Vec<_>>(),
+        [1, 2, 4, 9]
+    );
+    for error in &errors {
+        let span = &xml[error.span.clone()];
+        assert!(span.starts_with("<entry>"));
+        assert!(span.ends_with("</entry>"));
+    }
+    assert!(xml[errors[1].span.clone()].contains("1606.invalid"));
+    assert!(matches!(
+        errors[0].error.without_context(),
+        ResponseError::MissingTag("id")
+    ));
+    assert!(matches!(
+        errors[1].error.without_context(),
+        ResponseError::InvalidId(_)
+    ));
+    assert!(matches!(
+        errors[2].error.without_context(),
+        ResponseError::InvalidDateTime(_)
+    ));
+    assert!(matches!(
+        errors[3].error.without_context(),
+        ResponseError::InvalidHeader(_)
+    ));
+
+    // an arXiv API error is not recovered
+    let xml = include_str!("tests/query.xml").replace(
+        "http://arxiv.org/abs/nucl-ex/0408020v1",
+        "http://arxiv.org/api/errors#incorrect_id_format",
+    );
+    assert!(matches!(
+        parse_recover(xml.as_bytes()),
+        Err(ResponseError::Arxiv(_))
+    ));
+}
+
+#[test]
+fn test_error_context() {
+    use std::{error::Error, io::BufReader};
+
+    let xml = include_str!("tests/query.xml").replace(
+        "<name>Michael O. Distler</name>",
+        "<arxiv:affiliation>None</arxiv:affiliation>",
+    );
+    let entry_start = xml
+        .find("<entry>\n    <id>http://arxiv.org/abs/1606.02159v1")
+        .unwrap();
+
+    let err = parse(xml.as_bytes()).unwrap_err();
+    assert!(matches!(
+        err.without_context(),
+        ResponseError::MissingTag("name")
+    ));
+    assert_eq!(
+        err.context(),
+        Some(&ErrorContext {
+            index: Some(2),
+            id: Some(ArticleId::parse("1606.02159v1").unwrap()),
+            field: Some("authors[1].name".to_owned()),
+            position: Some(entry_start),
+        })
+    );
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "entry 2, id `1606.02159v1`, field `authors[1].name`, byte {entry_start}: missing tag `name`"
+        )
+    );
+
+    // the same error from a buffered source
+    let err = EntryReader::new(BufReader::new(xml.as_bytes()))
+        .unwrap()
+        .find_map(Result::err)
+        .unwrap();
+    assert_eq!(err.context().unwrap().position, Some(entry_start));
+    assert_eq!(
+        err.context().unwrap().field.as_deref(),
+        Some("authors[1].name")
+    );
+
+    // the underlying error is the source
+    let xml = include_str!("tests/query.xml").replace(
+        "<published>2016-06-07T18:32:01Z</published>",
+        "<published>7 June 2016</published>",
+    );
+    let err = parse(xml.as_bytes()).unwrap_err();
+    assert_eq!(err.context().unwrap().field.as_deref(), Some("published"));
+    assert!(err.source().unwrap().is::<chrono::ParseError>());
+
+    // malformed XML is located at the error
+    let xml =
+        include_str!("tests/query.xml").replace("proton radius</title>", "proton radius</titel>");
+    let err = parse(xml.as_bytes()).unwrap_err();
+    let context = err.context().unwrap();
+    assert_eq!(context.index, Some(2));
+    assert!(xml[context.position.unwrap()..].starts_with("</titel>"));
+    assert!(err.source().unwrap().is::<quick_xml::Error>());
+}
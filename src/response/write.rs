@@ -0,0 +1,292 @@
+//! # Atom feed writer
+//!
+//! Serialization of entries into the XML format returned by the arXiv API.
+use std::{borrow::Cow, io};
+
+use chrono::{DateTime, FixedOffset, SecondsFormat};
+use quick_xml::{
+    Writer,
+    events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event},
+};
+
+use super::{Entry, Pagination, Response};
+
+/// The title of a feed written by [`write`].
+const DEFAULT_TITLE: &str = "arXiv Query";
+
+/// The namespace declarations of the `<feed>` tag.
+const NAMESPACES: [(&str, &str); 3] = [
+    ("xmlns:opensearch", "http://a9.com/-/spec/opensearch/1.1/"),
+    ("xmlns:arxiv", "http://arxiv.org/schemas/atom"),
+    ("xmlns", "http://www.w3.org/2005/Atom"),
+];
+
+/// Write a [`Response`] as an XML document in the format returned by the arXiv API.
+///
+/// The document contains the feed header with the `updated` time and the opensearch pagination
+/// tags, followed by an `<entry>` for each entry with the `arxiv:` namespaced fields in the same
+/// order as the arXiv API. Passing the document to [`parse`](super::parse) returns the original
+/// response.
+///
+/// Text fields are written verbatim, except that `<` and any `&` which does not begin an escape
+/// sequence are escaped. This mirrors [`parse`](super::parse), which does not unescape text
+/// content, so that escape sequences such as `&amp;` in a parsed response are preserved. Author
+/// names are written using the [`Display`](std::fmt::Display) implementation of [`AuthorName`],
+/// and are read back with [`AuthorName::from_arxiv`].
+///
+/// [`AuthorName`]: super::AuthorName
+/// [`AuthorName::from_arxiv`]: super::AuthorName::from_arxiv
+///
+/// ### Example
+/// ```
+/// use rsxiv::response::{parse, write};
+///
+/// // abridged arXiv response
+/// let xml = // r#"<feed xmlns...
+#[doc = include_str!("tests/query_doc.txt")]
+/// let response = parse(xml.as_bytes()).unwrap();
+///
+/// let mut buffer = Vec::new();
+/// write(&mut buffer, &response).unwrap();
+/// assert_eq!(parse(&buffer).unwrap(), response);
+/// ```
+pub fn write<'a, 'r: 'a, W, T>(writer: W, response: &'a Response<T>) -> io::Result<()>
+where
+    W: io::Write,
+    &'a T: IntoIterator<Item = &'a Entry<'r>>,
+{
+    write_feed(
+        writer,
+        DEFAULT_TITLE,
+        &response.updated,
+        &response.pagination,
+        &response.entries,
+    )
+}
+
+/// Write a feed with the provided title containing the entries.
+pub(crate) fn write_feed<'a, 'r: 'a, W, I>(
+    writer: W,
+    title: &str,
+    updated: &DateTime<FixedOffset>,
+    pagination: &Pagination,
+    entries: I,
+) -> io::Result<()>
+where
+    W: io::Write,
+    I: IntoIterator<Item = &'a Entry<'r>>,
+{
+    let mut writer = AtomWriter::start(writer, title, updated, pagination)?;
+    for entry in entries {
+        writer.entry(entry)?;
+    }
+    writer.finish()
+}
+
+/// Write an arXiv API error feed, in the format returned by the arXiv API for invalid queries.
+#[cfg(feature = "mock-server")]
+pub(crate) fn write_error<W: io::Write>(
+    writer: W,
+    updated: &DateTime<FixedOffset>,
+    message: &str,
+) -> io::Result<()> {
+    let pagination = Pagination {
+        total_results: 1,
+        start_index: 0,
+        items_per_page: 1,
+    };
+    let mut writer = AtomWriter::start(writer, "arXiv Query: error", updated, &pagination)?;
+
+    let url = format!("http://arxiv.org/api/errors#{}", message.replace(' ', "_"));
+    writer.open("entry")?;
+    writer.text("id", &url)?;
+    writer.text("title", "Error")?;
+    writer.text("summary", message)?;
+    writer.text("updated", &datetime(updated))?;
+    writer.empty(
+        "link",
+        &[("href", &url), ("rel", "alternate"), ("type", "text/html")],
+    )?;
+    writer.open("author")?;
+    writer.text("name", "arXiv api core")?;
+    writer.close("author")?;
+    writer.close("entry")?;
+    writer.finish()
+}
+
+/// Escape text content.
+///
+/// The parser does not unescape text content, so text is written verbatim to preserve any escape
+/// sequences. Only the characters which cannot appear verbatim are escaped: `<`, and `&` when it
+/// does not begin an entity or character reference.
+fn escape_text(text: &str) -> Cow<'_, str> {
+    if !text.contains(['<', '&']) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut escaped = String::with_capacity(text.len());
+    for (idx, c) in text.char_indices() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '&' if !is_reference(&text[idx + 1..]) => escaped.push_str("&amp;"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Returns if the text following an `&` completes an entity or character reference, like `amp;`
+/// or `#x27;`.
+fn is_reference(rest: &str) -> bool {
+    let Some((name, _)) = rest.split_once(';') else {
+        return false;
+    };
+    if let Some(hex) = name.strip_prefix("#x") {
+        !hex.is_empty() && hex.bytes().all(|b| b.is_ascii_hexdigit())
+    } else if let Some(dec) = name.strip_prefix('#') {
+        !dec.is_empty() && dec.bytes().all(|b| b.is_ascii_digit())
+    } else {
+        !name.is_empty() && name.bytes().all(|b| b.is_ascii_alphanumeric())
+    }
+}
+
+/// Format a datetime in the style of the arXiv API, like `2025-01-30T01:23:42Z`.
+///
+/// Fractional seconds are only written if present, so that the datetime is parsed back exactly.
+fn datetime(dt: &DateTime<FixedOffset>) -> String {
+    dt.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// A thin wrapper around [`quick_xml::Writer`] with helpers for the tags in an arXiv feed.
+struct AtomWriter<W: io::Write> {
+    inner: Writer<W>,
+}
+
+impl<W: io::Write> AtomWriter<W> {
+    /// Write the XML declaration and the feed header, up to and including the pagination tags.
+    fn start(
+        writer: W,
+        title: &str,
+        updated: &DateTime<FixedOffset>,
+        pagination: &Pagination,
+    ) -> io::Result<Self> {
+        let mut writer = Self {
+            inner: Writer::new_with_indent(writer, b' ', 2),
+        };
+        writer
+            .inner
+            .write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        writer.inner.write_event(Event::Start(
+            BytesStart::new("feed").with_attributes(NAMESPACES),
+        ))?;
+        writer.text("title", title)?;
+        writer.text("updated", &datetime(updated))?;
+        writer.text(
+            "opensearch:itemsPerPage",
+            &pagination.items_per_page.to_string(),
+        )?;
+        writer.text(
+            "opensearch:totalResults",
+            &pagination.total_results.to_string(),
+        )?;
+        writer.text("opensearch:startIndex", &pagination.start_index.to_string())?;
+        Ok(writer)
+    }
+
+    /// Close the feed and write a trailing newline.
+    fn finish(mut self) -> io::Result<()> {
+        self.close("feed")?;
+        self.inner.get_mut().write_all(b"\n")
+    }
+
+    /// Write an opening tag.
+    fn open(&mut self, name: &str) -> io::Result<()> {
+        self.inner.write_event(Event::Start(BytesStart::new(name)))
+    }
+
+    /// Write a closing tag.
+    fn close(&mut self, name: &str) -> io::Result<()> {
+        self.inner.write_event(Event::End(BytesEnd::new(name)))
+    }
+
+    /// Write a tag containing text, escaped with [`escape_text`].
+    fn text(&mut self, name: &str, text: &str) -> io::Result<()> {
+        self.inner
+            .create_element(name)
+            .write_text_content(BytesText::from_escaped(escape_text(text)))?;
+        Ok(())
+    }
+
+    /// Write an empty tag with the attributes, which are escaped by [`quick_xml`].
+    fn empty(&mut self, name: &str, attributes: &[(&str, &str)]) -> io::Result<()> {
+        self.inner
+            .create_element(name)
+            .with_attributes(attributes.iter().copied())
+            .write_empty()?;
+        Ok(())
+    }
+
+    /// Write a single entry.
+    fn entry(&mut self, entry: &Entry<'_>) -> io::Result<()> {
+        self.open("entry")?;
+        self.text("id", &format!("http://arxiv.org/abs/{}", entry.id))?;
+        self.text("title", &entry.title)?;
+        self.text("updated", &datetime(&entry.updated))?;
+        if let Some(abs) = &entry.links.abs {
+            self.empty(
+                "link",
+                &[("href", abs), ("rel", "alternate"), ("type", "text/html")],
+            )?;
+        }
+        if let Some(pdf) = &entry.links.pdf {
+            self.empty(
+                "link",
+                &[
+                    ("title", "pdf"),
+                    ("href", pdf),
+                    ("rel", "related"),
+                    ("type", "application/pdf"),
+                ],
+            )?;
+        }
+        self.text("summary", &entry.summary)?;
+        for category in &entry.categories {
+            self.empty(
+                "category",
+                &[
+                    ("term", category),
+                    ("scheme", "http://arxiv.org/schemas/atom"),
+                ],
+            )?;
+        }
+        self.text("published", &datetime(&entry.published))?;
+        if let Some(comment) = &entry.comment {
+            self.text("arxiv:comment", comment)?;
+        }
+        self.empty(
+            "arxiv:primary_category",
+            &[("term", &entry.primary_category)],
+        )?;
+        if let Some(journal_ref) = &entry.journal_ref {
+            self.text("arxiv:journal_ref", journal_ref)?;
+        }
+        for author in &entry.authors {
+            self.open("author")?;
+            self.text("name", &author.name.to_string())?;
+            if let Some(affiliation) = &author.affiliation {
+                self.text("arxiv:affiliation", affiliation)?;
+            }
+            self.close("author")?;
+        }
+        if let Some(doi) = &entry.doi {
+            self.text("arxiv:doi", doi)?;
+        }
+        if let Some(doi) = &entry.links.doi {
+            self.empty(
+                "link",
+                &[("title", "doi"), ("href", doi), ("rel", "related")],
+            )?;
+        }
+        self.close("entry")
+    }
+}
@@ -1,26 +1,22 @@
 //! # Base response parsing methods
 //!
 //! Basic parsing methods for working with the arXiv API response.
-use std::borrow::Cow;
+use std::{borrow::Cow, ops::Range};
 
 use chrono::{DateTime, FixedOffset};
-
-use super::{Pagination, ResponseError};
-use crate::xml::{Event, Reader};
-
-/// A convenience trait to unwrap a `Result<Option<_>, ResponseError>` using the
-/// [`ResponseError::MissingTag`] variant.
-trait AndNotMissing<T> {
-    fn and_not_missing(self, tag: &'static str) -> Result<T, ResponseError>;
-}
-
-impl<T> AndNotMissing<T> for Result<Option<T>, ResponseError> {
-    fn and_not_missing(self, tag: &'static str) -> Result<T, ResponseError> {
-        self.and_then(|val| val.ok_or(ResponseError::MissingTag(tag)))
-    }
-}
-
-/// An empty tag which is expected to have an attribute named `term`.
+use quick_xml::{
+    errors::{Error as XmlError, IllFormedError},
+    events::attributes::Attributes,
+};
+
+use super::{Fields, Links, Pagination, ResponseError};
+use crate::{
+    id::ArticleId,
+    xml::{Event, Reader},
+};
+
+/// An empty tag with attributes, such as a `category` tag which is expected to have an attribute
+/// named `term`.
 pub struct Term<'r> {
     inner: quick_xml::events::BytesStart<'r>,
 }
@@ -39,13 +35,31 @@ impl<'r> Term<'r> {
     }
 }
 
+/// The error returned if the input ends before the closing tag with the provided name.
+pub(super) fn missing_end_tag(name: &str) -> ResponseError {
+    ResponseError::Parse(IllFormedError::MissingEndTag(name.to_owned()).into())
+}
+
+/// Returns if the error is a [`missing_end_tag`] error, which is located at the end of the input.
+pub(super) fn is_missing_end_tag(error: &ResponseError) -> bool {
+    matches!(
+        error,
+        ResponseError::Parse(XmlError::IllFormed(IllFormedError::MissingEndTag(_)))
+    )
+}
+
 /// A reader with methods specialized for the arXiv API response.
 ///
-/// The call order of the methods are very important, since we expect the tags to be in a specific
-/// order. However, the methods are implemented so that repeated calls to the same search met will
-/// not read beyond the current entry, with the exception of [`Self::next_id`].
+/// After reading the header with [`Self::init`], each entry is read in a single pass with
+/// [`Self::next_entry`].
 pub struct ResponseReader<'r> {
     xml_reader: Reader<'r>,
+    /// The index of the most recent `<entry>`, if any.
+    index: Option<usize>,
+    /// The byte offset of the most recent `<entry>`.
+    entry_start: usize,
+    /// The fields read from each entry.
+    fields: Fields,
 }
 
 impl<'r> ResponseReader<'r> {
@@ -59,9 +73,23 @@ impl<'r> ResponseReader<'r> {
         Ok((updated, pagination, resp))
     }
 
-    fn new(xml: &'r [u8]) -> Result<Self, ResponseError> {
+    /// Initialize the driver without parsing any header information, for instance to read a
+    /// single buffered `<entry>`.
+    pub fn new(xml: &'r [u8]) -> Result<Self, ResponseError> {
         let driver = Reader::new(xml);
-        Ok(Self { xml_reader: driver })
+        Ok(Self {
+            xml_reader: driver,
+            index: None,
+            entry_start: 0,
+            fields: Fields::ALL,
+        })
+    }
+
+    /// Set the fields to read from the remaining entries. The elements of the other fields are
+    /// skipped without reading their contents, and are missing from the [`RawEntry`].
+    pub fn fields(&mut self, fields: Fields) -> &mut Self {
+        self.fields = fields;
+        self
     }
 
     fn read_updated(&mut self) -> Result<DateTime<FixedOffset>, ResponseError> {
@@ -103,251 +131,264 @@ impl<'r> ResponseReader<'r> {
         })
     }
 
-    /// Read the contents of the next `<id>` tag, stripping the URL prefix and raising an error if
-    /// it is an `error-style` identifier. If this method returns `Ok(Some(_))`, the cursor is
-    /// placed immediately after the closing `id` tag. Otherwise, the cursor position is at the end
-    /// of the file.
+    /// Read the next `<entry>` in a single pass, returning the identifier with the URL prefix
+    /// stripped, and the contents of the entry. Returns `None` if there are no more entries.
     ///
-    /// This method is implemented in this way since some arxiv responses are malformed and to not
-    /// even contain an `<id>` identifier. Instead of trying to parse these entries or worry about
-    /// errors, we just skip the entries automatically.
-    pub fn next_id(&mut self) -> Result<Option<&'r [u8]>, ResponseError> {
-        match self.xml_reader.find_raw_matching_tag(|t| t == b"id")? {
-            Some(url) => {
-                if url.starts_with(b"http://arxiv.org/api/errors#") {
-                    match self
-                        .xml_reader
-                        .find_text_matching_tag(|t| t == b"summary")?
-                    {
-                        Some(contents) => Err(ResponseError::Arxiv(contents.into())),
-                        None => Err(ResponseError::InvalidError(
-                            "missing `summary` tag".to_owned(),
-                        )),
-                    }
-                } else {
-                    match url.strip_prefix(b"http://arxiv.org/abs/") {
-                        Some(id_bytes) => Ok(Some(id_bytes)),
-                        None => Err(ResponseError::InvalidHeader(format!(
-                            "`id` tag in unexpected format: {}",
-                            String::from_utf8_lossy(url)
-                        ))),
-                    }
-                }
+    /// An entry without an `<id>` tag is skipped, since some arXiv responses are malformed and
+    /// contain such entries. Use [`Self::next_raw_entry`] to also read these entries.
+    pub fn next_entry(&mut self) -> Result<Option<(&'r [u8], RawEntry<'r>)>, ResponseError> {
+        while let Some(entry) = self.next_raw_entry()? {
+            match entry.id() {
+                Ok(Some(id)) => return Ok(Some((id, entry))),
+                Ok(None) => {}
+                Err(err) => return Err(self.entry_error(err.in_field("id"), None)),
             }
-            None => Ok(None),
-        }
-    }
-
-    /// Find the next tag with the provided `name`, but do not read an end tag named
-    /// `limit`.
-    fn next_tag_with_name_limit(
-        &mut self,
-        name: &str,
-        limit: &str,
-    ) -> Result<Option<Cow<'r, str>>, ResponseError> {
-        match self.xml_reader.find_before(
-            |event| match event {
-                Event::Start(bytes_start) if bytes_start.name().0 == name.as_bytes() => {
-                    Some(bytes_start)
-                }
-                _ => None,
-            },
-            |event| {
-                matches!(event,
-                Event::End(bytes_end) if bytes_end.name().0 == limit.as_bytes())
-            },
-        )? {
-            Some(bytes_start) => Ok(Some(self.xml_reader.read_text(&bytes_start)?)),
-            None => Ok(None),
         }
+        Ok(None)
     }
 
-    /// Returns the contents of the next `<title>' tag in the entry, but not reading beyond the
-    /// current entry.
-    #[inline]
-    pub fn next_title(&mut self) -> Result<Cow<'r, str>, ResponseError> {
-        self.next_tag_with_name_limit("title", "entry")
-            .and_not_missing("title")
+    /// Attach the location of the most recent entry, and the identifier of the entry if it is
+    /// known, to an error.
+    ///
+    /// Errors returned by the arXiv API are not specific to an entry, and are returned unchanged.
+    pub fn entry_error(&self, error: ResponseError, id: Option<&[u8]>) -> ResponseError {
+        let position = match error.without_context() {
+            ResponseError::Arxiv(_) | ResponseError::InvalidError(_) => return error,
+            err if is_missing_end_tag(err) => self.xml_reader.position(),
+            ResponseError::Parse(_) => self.xml_reader.error_position(),
+            _ => self.entry_start,
+        };
+        let Some(index) = self.index else {
+            return error;
+        };
+        let id = id.and_then(|id| ArticleId::parse_bytes(id).ok());
+        error.in_entry(index, id, position)
     }
 
-    /// Returns the contents of the next `<updated>' tag in the entry, but not reading beyond the
-    /// current entry.
-    #[inline]
-    pub fn next_updated(&mut self) -> Result<Cow<'r, str>, ResponseError> {
-        self.next_tag_with_name_limit("updated", "entry")
-            .and_not_missing("updated")
+    /// Read the next `<entry>` in a single pass. Returns `None` if there are no more entries.
+    ///
+    /// The child elements of the entry may be in any order, and unrecognized elements and the
+    /// elements of fields which were not [selected](Self::fields) are skipped. Text is borrowed from the input and attributes are only decoded when they are
+    /// requested from the [`RawEntry`].
+    ///
+    /// If the identifier of the entry is an error URL, the error message in the `<summary>` tag is
+    /// returned as an error. Other errors contain the location of the entry.
+    pub fn next_raw_entry(&mut self) -> Result<Option<RawEntry<'r>>, ResponseError> {
+        self.read_raw_entry()
+            .map_err(|err| self.entry_error(err, None))
     }
 
-    /// Returns the contents of the next `<summary>' tag in the entry, but not reading beyond the
-    /// current entry.
-    #[inline]
-    pub fn next_summary(&mut self) -> Result<Cow<'r, str>, ResponseError> {
-        self.next_tag_with_name_limit("summary", "entry")
-            .and_not_missing("summary")
-    }
+    /// The implementation of [`Self::next_raw_entry`], without the error context.
+    pub(super) fn read_raw_entry(&mut self) -> Result<Option<RawEntry<'r>>, ResponseError> {
+        let Some(bytes_start) = self.xml_reader.find_matching_tag(|t| t == b"entry")? else {
+            return Ok(None);
+        };
 
-    /// Find an empty tag with the given name, not exceeding any event on which to halt.
-    fn next_term<H>(&mut self, name: &str, halt: H) -> Result<Option<Term<'r>>, ResponseError>
-    where
-        H: FnMut(&Event<'r>) -> bool,
-    {
-        match self.xml_reader.find_before(
-            |event| match event {
-                Event::Empty(bytes_start)
-                    if bytes_start.local_name().as_ref() == name.as_bytes() =>
-                {
-                    Some(bytes_start)
-                }
-                _ => None,
-            },
-            halt,
-        )? {
-            Some(bytes_start) => Ok(Some(Term { inner: bytes_start })),
-            None => Ok(None),
+        let mut entry = RawEntry::default();
+        // the opening tag is `bytes_start` enclosed in `<` and `>`
+        entry.span.start = self.xml_reader.position() - bytes_start.len() - 2;
+        self.entry_start = entry.span.start;
+        self.index = Some(self.index.map_or(0, |index| index + 1));
+
+        loop {
+            let Some(event) = self.xml_reader.read()? else {
+                return Err(missing_end_tag("entry"));
+            };
+            match event {
+                Event::End(bytes_end) if bytes_end.name().0 == b"entry" => break,
+                Event::End(_) => {}
+                Event::Start(bytes_start) => match bytes_start.local_name().as_ref() {
+                    b"id" => {
+                        let raw = self.xml_reader.read_raw(&bytes_start)?;
+                        entry.url.get_or_insert(raw);
+                    }
+                    // tags with attributes may also be written with an explicit closing tag
+                    b"category" if self.fields.contains(Fields::CATEGORIES) => {
+                        self.xml_reader.skip(&bytes_start)?;
+                        entry.categories.push(Term { inner: bytes_start });
+                    }
+                    b"primary_category" if self.fields.contains(Fields::PRIMARY_CATEGORY) => {
+                        self.xml_reader.skip(&bytes_start)?;
+                        entry
+                            .primary_category
+                            .get_or_insert(Term { inner: bytes_start });
+                    }
+                    b"link" if self.fields.contains(Fields::LINKS) => {
+                        let attributes = self.xml_reader.raw_attributes(&bytes_start, false);
+                        self.xml_reader.skip(&bytes_start)?;
+                        entry.links.push(attributes);
+                    }
+                    b"author" if self.fields.contains(Fields::AUTHORS) => {
+                        entry.authors.push(self.read_author()?);
+                    }
+                    name => match entry.text_slot(name) {
+                        Some((field, slot)) if self.fields.contains(field) => {
+                            let text = self.xml_reader.read_text(&bytes_start)?;
+                            slot.get_or_insert(text);
+                        }
+                        _ => self.xml_reader.skip(&bytes_start)?,
+                    },
+                },
+                Event::Empty(bytes_start) => match bytes_start.local_name().as_ref() {
+                    b"category" if self.fields.contains(Fields::CATEGORIES) => {
+                        entry.categories.push(Term { inner: bytes_start });
+                    }
+                    b"primary_category" if self.fields.contains(Fields::PRIMARY_CATEGORY) => {
+                        entry
+                            .primary_category
+                            .get_or_insert(Term { inner: bytes_start });
+                    }
+                    b"link" if self.fields.contains(Fields::LINKS) => entry
+                        .links
+                        .push(self.xml_reader.raw_attributes(&bytes_start, true)),
+                    name => {
+                        if let Some((field, slot)) = entry.text_slot(name)
+                            && self.fields.contains(field)
+                        {
+                            slot.get_or_insert(Cow::Borrowed(""));
+                        }
+                    }
+                },
+            }
         }
-    }
-
-    /// Read the next `category` tag.
-    pub fn next_category(&mut self) -> Result<Option<Term<'r>>, ResponseError> {
-        self.next_term("category", |event| match event {
-            Event::Start(bytes_start) => bytes_start.name().0 == b"published",
-            Event::End(bytes_end) => bytes_end.name().0 == b"entry",
-            _ => false,
-        })
-    }
-
-    /// Returns the contents of the next `<published>' tag in the entry, but not reading beyond the
-    /// current entry.
-    #[inline]
-    pub fn next_published(&mut self) -> Result<Cow<'r, str>, ResponseError> {
-        self.next_tag_with_name_limit("published", "entry")
-            .and_not_missing("published")
-    }
-
-    /// Read the next `comment` tag.
-    ///
-    /// This will not read past any of the following tags:
-    /// - `Empty(primary_category)`
-    pub fn next_comment(&mut self) -> Result<Option<Cow<'r, str>>, ResponseError> {
-        match self.xml_reader.find_before(
-            |entry| match entry {
-                Event::Start(bytes_start) if bytes_start.local_name().as_ref() == b"comment" => {
-                    Some(bytes_start)
-                }
-                _ => None,
-            },
-            |entry| matches!(entry, Event::Empty(bytes_start) if bytes_start.local_name().as_ref() == b"primary_category"),
-        )? {
-            Some(bytes_start) => Ok(Some(self.xml_reader.read_text(&bytes_start)?)),
-            None => Ok(None),
+        entry.span.end = self.xml_reader.position();
+
+        if let Some(url) = entry.url
+            && url.starts_with(b"http://arxiv.org/api/errors#")
+        {
+            if !self.fields.contains(Fields::SUMMARY) {
+                // the error message is in the skipped summary, so read the entry again
+                let mut reader = ResponseReader::new(&self.xml_reader.input()[entry.span])?;
+                return reader.read_raw_entry();
+            }
+            return Err(match entry.summary {
+                Some(contents) => ResponseError::Arxiv(contents.into()),
+                None => ResponseError::InvalidError("missing `summary` tag".to_owned()),
+            });
         }
-    }
 
-    /// Read the next `primary_category` tag.
-    pub fn next_primary_category(&mut self) -> Result<Term<'r>, ResponseError> {
-        self.next_term("primary_category", |entry| match entry {
-            Event::End(bytes_end) => bytes_end.name().0 == b"entry",
-            _ => false,
-        })
-        .and_not_missing("primary_category")
+        Ok(Some(entry))
     }
 
-    /// Read the next `journal_ref` tag.
-    pub fn next_journal_ref(&mut self) -> Result<Option<Cow<'r, str>>, ResponseError> {
-        match self.xml_reader.find_before(
-            |entry| match entry {
-                Event::Start(bytes_start)
-                    if bytes_start.local_name().as_ref() == b"journal_ref" =>
-                {
-                    Some(bytes_start)
-                }
-                _ => None,
-            },
-            |entry| match entry {
+    /// After entering an `<author>` tag, read the contents of the author up to and including the
+    /// closing `</author>` tag.
+    fn read_author(&mut self) -> Result<RawAuthor<'r>, ResponseError> {
+        let mut author = RawAuthor::default();
+        loop {
+            let Some(event) = self.xml_reader.read()? else {
+                return Err(missing_end_tag("author"));
+            };
+            match event {
+                Event::End(bytes_end) if bytes_end.name().0 == b"author" => break,
                 Event::Start(bytes_start) => {
-                    matches!(bytes_start.local_name().as_ref(), b"author" | b"doi")
-                }
-                Event::End(bytes_start) => {
-                    matches!(bytes_start.name().0, b"entry")
+                    let slot = match bytes_start.local_name().as_ref() {
+                        b"name" => &mut author.name,
+                        b"affiliation" => &mut author.affiliation,
+                        _ => {
+                            self.xml_reader.skip(&bytes_start)?;
+                            continue;
+                        }
+                    };
+                    let text = self.xml_reader.read_text(&bytes_start)?;
+                    slot.get_or_insert(text);
                 }
-                _ => false,
-            },
-        )? {
-            Some(bytes_start) => Ok(Some(self.xml_reader.read_text(&bytes_start)?)),
-            None => Ok(None),
+                Event::End(_) | Event::Empty(_) => {}
+            }
         }
+        Ok(author)
     }
+}
 
-    /// Enter the next `<author>` tag if present, not reading beyond the current entry.
-    ///
-    /// If this function returns `Ok(true)`, an `<author>` tag was found and the cursor is
-    /// immmediately following the tag.
-    ///
-    /// If this function returns `Ok(false)`, the next tag is not an `<author>` tag.
-    ///
-    /// This will not read past any of the following tags:
-    /// - `Start(doi)`
-    pub fn next_author(&mut self) -> Result<bool, ResponseError> {
-        match self.xml_reader.find_before(
-            |entry| match entry {
-                Event::Start(bytes_start) if bytes_start.name().0 == b"author" => Some(()),
-                _ => None,
-            },
-            |entry| match entry {
-                Event::Start(bytes_start) => bytes_start.local_name().as_ref() == b"doi",
-                Event::End(bytes_start) => {
-                    matches!(bytes_start.name().0, b"entry")
-                }
-                Event::Empty(_) => false,
-            },
-        )? {
-            Some(()) => Ok(true),
-            None => Ok(false),
+/// The contents of an `<entry>`, read in a single pass by [`ResponseReader::next_entry`].
+///
+/// Text is borrowed from the input, and tags with attributes are stored undecoded.
+#[derive(Default)]
+pub struct RawEntry<'r> {
+    /// The byte range of the entry in the input, including the opening and closing tags.
+    pub span: Range<usize>,
+    /// The raw contents of the `<id>` tag, which is a URL.
+    pub url: Option<&'r [u8]>,
+    pub title: Option<Cow<'r, str>>,
+    pub updated: Option<Cow<'r, str>>,
+    pub summary: Option<Cow<'r, str>>,
+    pub published: Option<Cow<'r, str>>,
+    pub comment: Option<Cow<'r, str>>,
+    pub journal_ref: Option<Cow<'r, str>>,
+    pub doi: Option<Cow<'r, str>>,
+    pub primary_category: Option<Term<'r>>,
+    pub categories: Vec<Term<'r>>,
+    pub authors: Vec<RawAuthor<'r>>,
+    /// The raw attributes of each `<link>` tag.
+    pub links: Vec<&'r [u8]>,
+}
+
+impl<'r> RawEntry<'r> {
+    /// Returns the field and the slot for the text of the tag with the provided local name, if
+    /// any.
+    fn text_slot(&mut self, local_name: &[u8]) -> Option<(Fields, &mut Option<Cow<'r, str>>)> {
+        match local_name {
+            b"title" => Some((Fields::TITLE, &mut self.title)),
+            b"updated" => Some((Fields::UPDATED, &mut self.updated)),
+            b"summary" => Some((Fields::SUMMARY, &mut self.summary)),
+            b"published" => Some((Fields::PUBLISHED, &mut self.published)),
+            b"comment" => Some((Fields::COMMENT, &mut self.comment)),
+            b"journal_ref" => Some((Fields::JOURNAL_REF, &mut self.journal_ref)),
+            b"doi" => Some((Fields::DOI, &mut self.doi)),
+            _ => None,
         }
     }
 
-    /// After entering an `<author>` tag, find the next `<name>` tag in the author.
-    ///
-    /// This will not read past the closing `</author>` tag.
-    pub fn next_author_name(&mut self) -> Result<Cow<'r, str>, ResponseError> {
-        self.next_tag_with_name_limit("name", "author")
-            .and_not_missing("name")
-    }
+    /// Returns the identifier of the entry with the URL prefix stripped, or `None` if the entry does
+    /// not have an `<id>` tag.
+    pub fn id(&self) -> Result<Option<&'r [u8]>, ResponseError> {
+        let Some(url) = self.url else {
+            return Ok(None);
+        };
 
-    /// After entering an `<author>` tag, find the next `<affiliation>` tag in the author.
-    ///
-    /// This will not read past the closing `</author>` tag.
-    pub fn next_author_affiliation(&mut self) -> Result<Option<Cow<'r, str>>, ResponseError> {
-        self.next_tag_with_name_limit("affiliation", "author")
+        match url.strip_prefix(b"http://arxiv.org/abs/") {
+            Some(id_bytes) => Ok(Some(id_bytes)),
+            None => Err(ResponseError::InvalidHeader(format!(
+                "`id` tag in unexpected format: {}",
+                String::from_utf8_lossy(url)
+            ))),
+        }
     }
 
-    /// Read the next `doi` tag.
-    ///
-    /// This will not read past any of the following tags:
-    /// - `Start(comment)`
-    /// - `Start(journal_ref)`
-    /// - `Empty(primary_category)`
-    pub fn next_doi(&mut self) -> Result<Option<Cow<'r, str>>, ResponseError> {
-        match self.xml_reader.find_before(
-            |entry| match entry {
-                Event::Start(bytes_start) if bytes_start.local_name().as_ref() == b"doi" => {
-                    Some(bytes_start)
+    /// Decode the `<link>` tags of the entry. Attribute values are borrowed from the input unless
+    /// they contain escape sequences.
+    pub fn links(&self) -> Result<Links<'r>, ResponseError> {
+        let mut links = Links::default();
+        for raw in &self.links {
+            let (mut href, mut rel, mut title) = (None, None, None);
+            let mut attributes = Attributes::new(std::str::from_utf8(raw)?, 0);
+            attributes.with_checks(false);
+            for attribute in attributes {
+                let attribute = attribute?;
+                let slot = match attribute.key.as_ref() {
+                    b"href" => &mut href,
+                    b"rel" => &mut rel,
+                    b"title" => &mut title,
+                    _ => continue,
+                };
+                if slot.is_none() {
+                    *slot = Some(attribute.unescape_value()?);
                 }
-                _ => None,
-            },
-            |entry| match entry {
-                Event::End(bytes_start) => {
-                    matches!(bytes_start.name().0, b"entry")
-                }
-                _ => false,
-            },
-        )? {
-            Some(bytes_start) => Ok(Some(self.xml_reader.read_text(&bytes_start)?)),
-            None => Ok(None),
+            }
+            if let Some(href) = href {
+                links.push(href, rel.as_deref(), title.as_deref());
+            }
         }
+        Ok(links)
     }
 }
 
+/// The contents of an `<author>`, read by [`ResponseReader::next_entry`].
+#[derive(Default)]
+pub struct RawAuthor<'r> {
+    pub name: Option<Cow<'r, str>>,
+    pub affiliation: Option<Cow<'r, str>>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,62 +411,55 @@ mod tests {
         );
 
         // first entry
-        assert_eq!(reader.next_id()?, Some("nucl-ex/0408020v1".as_bytes()),);
+        let (id, entry) = reader.next_entry()?.unwrap();
+        assert_eq!(id, "nucl-ex/0408020v1".as_bytes());
+        let categories: Vec<String> = entry
+            .categories
+            .iter()
+            .map(|term| term.get().unwrap().into_owned())
+            .collect();
+        assert_eq!(categories, ["nucl-ex", "hep-ph"]);
+        assert_eq!(entry.published.unwrap(), "2004-08-18T23:13:32Z");
         assert_eq!(
-            reader
-                .next_category()?
-                .map(|term| String::from(term.get().unwrap())),
-            Some("nucl-ex".to_string())
-        );
-        assert_eq!(
-            reader
-                .next_category()?
-                .map(|term| String::from(term.get().unwrap())),
-            Some("hep-ph".to_string())
-        );
-        assert!(reader.next_category()?.is_none());
-
-        assert_eq!(reader.next_published()?, "2004-08-18T23:13:32Z");
-
-        assert_eq!(
-            reader.next_comment()?,
+            entry.comment,
             Some(Cow::Borrowed(
                 "13 pages, 10 figures. Proposal for a comparison of electron-proton and positron-proton scattering at VEPP-3"
             ))
         );
 
         // second entry; skip
-        assert!(reader.next_id()?.is_some());
+        assert!(reader.next_entry()?.is_some());
 
         // third entry
-        assert_eq!(reader.next_id()?, Some("1606.02159v1".as_bytes()));
-
-        assert_eq!(reader.next_journal_ref()?, None);
-
-        assert!(reader.next_author()?);
-        assert!(reader.next_author()?);
-        assert!(!reader.next_author()?);
+        let (id, entry) = reader.next_entry()?.unwrap();
+        assert_eq!(id, "1606.02159v1".as_bytes());
+        assert_eq!(entry.journal_ref, None);
+        assert_eq!(entry.authors.len(), 2);
 
         // fourth entry
-        assert_eq!(reader.next_id()?.unwrap(), "1610.08734v3".as_bytes());
-        assert!(reader.next_author()?);
-        assert_eq!(reader.next_author_name()?, "Yangmei Li");
-        assert_eq!(reader.next_author_affiliation()?, None);
-        assert_eq!(reader.next_author_affiliation()?, None);
+        let (id, entry) = reader.next_entry()?.unwrap();
+        assert_eq!(id, "1610.08734v3".as_bytes());
+        assert_eq!(entry.authors[0].name.as_deref(), Some("Yangmei Li"));
+        assert_eq!(entry.authors[0].affiliation, None);
         assert_eq!(
-            reader.next_doi()?.unwrap(),
-            "10.1103/PhysRevAccelBeams.20.101301"
+            entry.doi.as_deref(),
+            Some("10.1103/PhysRevAccelBeams.20.101301")
         );
-        assert!(reader.next_doi()?.is_none());
-        assert!(reader.next_doi()?.is_none());
 
         // fifth entry
-        assert_eq!(reader.next_id()?.unwrap(), "2102.00018v2".as_bytes());
+        let (id, entry) = reader.next_entry()?.unwrap();
+        assert_eq!(id, "2102.00018v2".as_bytes());
         assert_eq!(
-            reader.next_journal_ref()?.unwrap(),
-            "Phys. Rev. D 103, 096005 (2021)"
+            entry.journal_ref.as_deref(),
+            Some("Phys. Rev. D 103, 096005 (2021)")
         );
 
+        // remaining entries
+        for _ in 5..10 {
+            assert!(reader.next_entry()?.is_some());
+        }
+        assert!(reader.next_entry()?.is_none());
+
         Ok(())
     }
 }
@@ -1,7 +1,12 @@
 //! # XML response parsing
 //!
-//! This module implements an XML parsing layer on top of [`quick_xml::Reader`]. The main benefit
-//! of this implementation is the iteration method [`Reader::find_before`].
+//! This module implements an XML parsing layer on top of [`quick_xml::Reader`]. The [`Reader`]
+//! reads from an in-memory buffer and returns text and raw attributes borrowed from the input,
+//! with methods to read or skip the contents of a tag.
+//!
+//! The response header is located with [`Reader::find_text_matching_tag`], which is built on
+//! [`Reader::find_before`]. The entries are read tag by tag by
+//! [`ResponseReader::next_raw_entry`](crate::response::ResponseReader::next_raw_entry).
 use std::borrow::Cow;
 
 use quick_xml::{
@@ -12,6 +17,7 @@ use quick_xml::{
 
 /// Similar to [`quick_xml::events::Event`], but only containing the events which are important for
 /// this module.
+#[derive(Clone)]
 pub enum Event<'r> {
     Start(BytesStart<'r>),
     End(BytesEnd<'r>),
@@ -19,6 +25,7 @@ pub enum Event<'r> {
 }
 
 /// A wrapper for [`quick_xml::Reader`] with peeking and other convenience iteration methods.
+#[derive(Clone)]
 pub struct Reader<'r> {
     reader: QReader<&'r [u8]>,
     peeked: Option<Event<'r>>,
@@ -58,6 +65,18 @@ impl<'r> Reader<'r> {
         self.read_inner()
     }
 
+    /// The byte offset of the reader in the input, immediately after the most recently read event.
+    ///
+    /// This does not account for a peeked event.
+    pub fn position(&self) -> usize {
+        self.reader.buffer_position() as usize
+    }
+
+    /// The byte offset of the start of the markup which caused the most recent error.
+    pub fn error_position(&self) -> usize {
+        self.reader.error_position() as usize
+    }
+
     /// Read the text between the provided start tag and the matching end tag.
     pub fn read_text(&mut self, start: &BytesStart<'_>) -> Result<Cow<'r, str>, Error> {
         self.reader.read_text(start.to_end().name())
@@ -97,7 +116,7 @@ impl<'r> Reader<'r> {
     }
 
     /// Find an opening tag with the provided tag name.
-    fn find_matching_tag(
+    pub fn find_matching_tag(
         &mut self,
         mut f: impl FnMut(&[u8]) -> bool,
     ) -> Result<Option<BytesStart<'r>>, Error> {
@@ -123,17 +142,29 @@ impl<'r> Reader<'r> {
         }
     }
 
-    /// Returns the raw tag contents (as bytes) of the first tag matching the provided tag name.
-    pub fn find_raw_matching_tag(
-        &mut self,
-        f: impl FnMut(&[u8]) -> bool,
-    ) -> Result<Option<&'r [u8]>, Error> {
-        if let Some(bytes_start) = self.find_matching_tag(f)? {
-            // read the bytes
-            let span = self.reader.read_to_end(bytes_start.to_end().name())?;
-            Ok(self.xml.get(span.start as usize..span.end as usize))
-        } else {
-            Ok(None)
-        }
+    /// Returns the raw contents (as bytes) between the provided start tag and the matching end
+    /// tag.
+    pub fn read_raw(&mut self, start: &BytesStart<'_>) -> Result<&'r [u8], Error> {
+        let span = self.reader.read_to_end(start.to_end().name())?;
+        Ok(&self.xml[span.start as usize..span.end as usize])
+    }
+
+    /// The raw attributes of the provided start tag, borrowed from the input. The tag must be the
+    /// most recently read event, which is `empty` if the tag is self-closing.
+    pub fn raw_attributes(&self, start: &BytesStart<'_>, empty: bool) -> &'r [u8] {
+        // the tag is followed by `>`, or by `/>` if it is self-closing
+        let end = self.position() - 1 - usize::from(empty);
+        &self.xml[end - start.attributes_raw().len()..end]
+    }
+
+    /// The input which is being read.
+    pub fn input(&self) -> &'r [u8] {
+        self.xml
+    }
+
+    /// Skip past the matching end tag of the provided start tag.
+    pub fn skip(&mut self, start: &BytesStart<'_>) -> Result<(), Error> {
+        self.reader.read_to_end(start.to_end().name())?;
+        Ok(())
     }
 }